@@ -13,6 +13,71 @@ fn write_string_to_file<S: AsRef<str>>(path: &Utf8Path, data: S) {
         .unwrap_or_else(|e| panic!("Failed to write file `{}` with error: {}", data.as_ref(), e));
 }
 
+fn get_puzzle_link(puzzle_id: u16, puzzles_path: &str) -> String {
+    let puzzle_data = rukata_puzzle_data::get_file_data(puzzle_id).unwrap();
+    format!(
+        "[p{0:0>5} - {1}]({2}/p{0:0>5}/index.html)",
+        puzzle_id,
+        puzzle_data.get_title(),
+        puzzles_path
+    )
+}
+
+fn get_puzzle_links<'a, I: Iterator<Item = &'a u16>>(puzzle_ids: I, puzzles_path: &str) -> String {
+    let links: Vec<String> = puzzle_ids
+        .map(|puzzle_id| get_puzzle_link(*puzzle_id, puzzles_path))
+        .collect();
+    if links.is_empty() {
+        "-".to_string()
+    } else {
+        links.join(", ")
+    }
+}
+
+/// The section added to a puzzle page listing the puzzles it requires and the puzzles it unlocks.
+fn get_prerequisites_section(puzzle_id: u16) -> String {
+    let puzzle_data = rukata_puzzle_data::get_file_data(puzzle_id).unwrap();
+    let required_by = rukata_puzzle_data::get_required_by(puzzle_id);
+    if puzzle_data.get_requires().is_empty() && required_by.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\n### Prerequisites\n");
+    section.push_str(&format!(
+        "\nRequires: {}\n",
+        get_puzzle_links(puzzle_data.get_requires().iter(), "..")
+    ));
+    section.push_str(&format!(
+        "\nUnlocks: {}\n",
+        get_puzzle_links(required_by.into_iter(), "..")
+    ));
+    section.push_str("\nSee the [prerequisite graph](../../prerequisites.md) for every puzzle.\n");
+    section
+}
+
+/// The section added to a multi-stage puzzle page listing the stages unlocked after the starter files.
+fn get_stages_section(puzzle_id: u16) -> String {
+    let puzzle_data = rukata_puzzle_data::get_file_data(puzzle_id).unwrap();
+    if puzzle_data.get_stages().is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from("\n### Stages\n");
+    section.push_str(&format!(
+        "\nThis puzzle has {} stages, `rukata check` unlocks each one once the stage before it passes.\n\n",
+        puzzle_data.get_stage_count()
+    ));
+    section.push_str("1. The starter files\n");
+    for stage in puzzle_data.get_stages() {
+        section.push_str(&format!(
+            "1. **{}** - {}\n",
+            stage.get_title(),
+            stage.get_description()
+        ));
+    }
+    section
+}
+
 fn generate_markdown_files(companion_path: &Utf8Path) {
     let src_path = companion_path.join("src");
     let output_path = src_path.join("puzzles");
@@ -48,7 +113,7 @@ fn generate_markdown_files(companion_path: &Utf8Path) {
     let mut category_puzzle_map = HashMap::new();
     let mut library_puzzle_map = HashMap::new();
 
-    for key in rukata_puzzle_data::get_id_list() {
+    for key in rukata_puzzle_data::get_ordered_id_list() {
         let puzzle_data = rukata_puzzle_data::get_file_data(*key).unwrap();
         let puzzle_title = puzzle_data.get_title();
         let puzzle_id = puzzle_data.get_id();
@@ -90,7 +155,9 @@ fn generate_markdown_files(companion_path: &Utf8Path) {
 
         {
             let readme_file_path = puzzle_path.join("README.md");
-            let readme_string = puzzle_data.get_readme_str();
+            let readme_string = puzzle_data.get_readme_str().to_string()
+                + &get_stages_section(*puzzle_id)
+                + &get_prerequisites_section(*puzzle_id);
             fs::write(readme_file_path.clone(), readme_string).unwrap_or_else(|e| {
                 panic!(
                     "Failed to write file `{}` with error: {}",
@@ -210,12 +277,98 @@ fn generate_markdown_files(companion_path: &Utf8Path) {
     write_string_to_file(&categories_file_path, categories_content);
     // endregion
 
+    // region Create `src/prerequisites.md`.
+    let prerequisites_template_path = template_path.join("prerequisites_template.md");
+    let mut prerequisites_content = read_file_to_string(&prerequisites_template_path);
+
+    prerequisites_content.push_str("| Puzzle | Requires | Unlocks |\n| --- | --- | --- |\n");
+    for puzzle_id in rukata_puzzle_data::get_ordered_id_list() {
+        let puzzle_data = rukata_puzzle_data::get_file_data(*puzzle_id).unwrap();
+        prerequisites_content.push_str(
+            format!(
+                "| {} | {} | {} |\n",
+                get_puzzle_link(*puzzle_id, "./puzzles"),
+                get_puzzle_links(puzzle_data.get_requires().iter(), "./puzzles"),
+                get_puzzle_links(
+                    rukata_puzzle_data::get_required_by(*puzzle_id).into_iter(),
+                    "./puzzles"
+                ),
+            )
+            .as_str(),
+        );
+    }
+
+    let prerequisites_file_path = src_path.join("prerequisites.md");
+    write_string_to_file(&prerequisites_file_path, prerequisites_content);
+    // endregion
+
+    // region Create `src/tracks.md` and a page for each track in `src/tracks`.
+    let tracks_path = src_path.join("tracks");
+    if tracks_path.exists() {
+        fs::remove_dir_all(&tracks_path)
+            .unwrap_or_else(|e| panic!("Failed to delete `{}` with error: {}", tracks_path, e));
+    }
+    fs::create_dir_all(&tracks_path).unwrap_or_else(|e| {
+        panic!(
+            "Failed to create directory `{}` with error: {}",
+            tracks_path, e
+        )
+    });
+
+    let tracks_template_path = template_path.join("tracks_template.md");
+    let mut tracks_content = read_file_to_string(&tracks_template_path);
+    let mut tracks_summary_content = String::from("\n- [Tracks](./tracks.md)\n");
+
+    for track in rukata_puzzle_data::get_tracks() {
+        tracks_content.push_str(
+            format!(
+                "- [{}](./tracks/{}.md) - {}\n",
+                track.get_title(),
+                track.get_id(),
+                track.get_description()
+            )
+            .as_str(),
+        );
+        tracks_summary_content.push_str(
+            format!(
+                "   - [{}](./tracks/{}.md)\n",
+                track.get_title(),
+                track.get_id()
+            )
+            .as_str(),
+        );
+
+        let mut track_content =
+            format!("# {}\n\n{}\n\n", track.get_title(), track.get_description());
+        for puzzle_id in track.get_puzzles() {
+            let puzzle_data = rukata_puzzle_data::get_file_data(*puzzle_id).unwrap();
+            track_content.push_str(
+                format!(
+                    "1. {} - {}\n",
+                    get_puzzle_link(*puzzle_id, "../puzzles"),
+                    puzzle_data.get_difficulty()
+                )
+                .as_str(),
+            );
+        }
+        track_content
+            .push_str(format!("\n### Command\n`rukata track start {}`\n", track.get_id()).as_str());
+
+        let track_file_path = tracks_path.join(format!("{}.md", track.get_id()));
+        write_string_to_file(&track_file_path, track_content);
+    }
+
+    let tracks_file_path = src_path.join("tracks.md");
+    write_string_to_file(&tracks_file_path, tracks_content);
+    // endregion
+
     // region Create `src/SUMMARY.md` and `src/puzzles/index.md`.
     let summary_template_path = template_path.join("SUMMARY_template.md");
     let mut summary_content = read_file_to_string(&summary_template_path);
 
     let mut index_content = String::new();
     index_content.push_str("# Puzzle List\n\n");
+    summary_content.push_str(&tracks_summary_content);
     summary_content.push_str("\n- [Puzzle List](./puzzles/index.md)\n");
     for item in &index_puzzle_list {
         index_content
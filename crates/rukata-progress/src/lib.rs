@@ -0,0 +1,76 @@
+use crate::versioned_progress::VersionedProgress;
+use camino::Utf8PathBuf;
+use std::fs;
+
+pub mod versioned_progress;
+pub mod versions;
+
+#[derive(Clone, Debug)]
+pub struct ProgressHandler {
+    progress: VersionedProgress,
+    path: Utf8PathBuf,
+}
+
+impl ProgressHandler {
+    pub fn new(path: Utf8PathBuf) -> Result<ProgressHandler, String> {
+        if path.is_relative() {
+            return Err(format!("Given progress path is relative `{}`", path));
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(s) => match serde_json::from_str::<VersionedProgress>(&s) {
+                Ok(progress) => Ok(ProgressHandler { progress, path }),
+                Err(e) => Err(format!(
+                    "Failed to read progress `{}` with error: {}",
+                    path, e
+                )),
+            },
+            Err(_e) => Ok(ProgressHandler {
+                progress: VersionedProgress::default(),
+                path,
+            }),
+        }
+    }
+
+    pub fn get_path(&self) -> Utf8PathBuf {
+        self.path.clone()
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let json_string = match serde_json::to_string_pretty(&self.progress) {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to convert progress to String with error: {}",
+                    e
+                ))
+            }
+        };
+
+        let folder_path = match self.path.parent() {
+            Some(p) => p,
+            None => return Err(format!("Failed to obtain parent of path `{}`", self.path)),
+        };
+
+        if let Err(e) = fs::create_dir_all(folder_path) {
+            return Err(format!(
+                "Failed to create folder path `{}` with error: {}",
+                folder_path, e
+            ));
+        }
+
+        if let Err(e) = fs::write(&self.path, json_string) {
+            return Err(format!("Failed to save `{}` with error: {}", self.path, e));
+        }
+
+        Ok(())
+    }
+
+    pub fn get_mut_progress(&mut self) -> &mut versions::v1::Progress {
+        self.progress.get_mut_progress()
+    }
+
+    pub fn get_progress(&self) -> &versions::v1::Progress {
+        self.progress.get_progress()
+    }
+}
@@ -0,0 +1,28 @@
+use crate::versions;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "version")]
+pub enum VersionedProgress {
+    V1(versions::v1::Progress),
+}
+
+impl Default for VersionedProgress {
+    fn default() -> Self {
+        VersionedProgress::V1(versions::v1::Progress::default())
+    }
+}
+
+impl VersionedProgress {
+    pub fn get_mut_progress(&mut self) -> &mut versions::v1::Progress {
+        match self {
+            VersionedProgress::V1(ref mut progress) => progress,
+        }
+    }
+
+    pub fn get_progress(&self) -> &versions::v1::Progress {
+        match self {
+            VersionedProgress::V1(ref progress) => progress,
+        }
+    }
+}
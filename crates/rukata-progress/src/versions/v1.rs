@@ -0,0 +1,473 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckResult {
+    Passed,
+    Failed,
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PuzzleState {
+    NotStarted,
+    InProgress,
+    Completed,
+}
+
+impl fmt::Display for PuzzleState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PuzzleState::NotStarted => write!(f, "Not started"),
+            PuzzleState::InProgress => write!(f, "In progress"),
+            PuzzleState::Completed => write!(f, "Completed"),
+        }
+    }
+}
+
+/// Longest gap between two activities, in seconds, counted towards a timed run.
+///
+/// Longer gaps are taken as time away from the puzzle, so the active time of a run is an estimate.
+pub const IDLE_LIMIT: u64 = 15 * 60;
+
+/// Time spent on a puzzle from `generate` until the first passing check.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TimedRun {
+    pub(crate) started: DateTime<Utc>,
+    pub(crate) last_activity: DateTime<Utc>,
+    pub(crate) active_seconds: u64,
+    pub(crate) finished: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub(crate) failed_checks: usize,
+}
+
+impl TimedRun {
+    fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            started: timestamp,
+            last_activity: timestamp,
+            active_seconds: 0,
+            finished: None,
+            failed_checks: 0,
+        }
+    }
+
+    pub fn get_started(&self) -> &DateTime<Utc> {
+        &self.started
+    }
+
+    pub fn get_finished(&self) -> Option<&DateTime<Utc>> {
+        self.finished.as_ref()
+    }
+
+    /// Estimated seconds spent on the puzzle, from the gaps between activities without idle gaps.
+    pub fn get_active_seconds(&self) -> u64 {
+        self.active_seconds
+    }
+
+    /// Checks that failed before the run finished.
+    pub fn get_failed_checks(&self) -> usize {
+        self.failed_checks
+    }
+
+    fn record_activity(&mut self, timestamp: DateTime<Utc>) {
+        if timestamp <= self.last_activity {
+            return;
+        }
+
+        let gap = (timestamp - self.last_activity).num_seconds().max(0) as u64;
+        self.active_seconds += gap.min(IDLE_LIMIT);
+        self.last_activity = timestamp;
+    }
+}
+
+/// Ease factor given to a puzzle the first time it is reviewed.
+pub const INITIAL_EASE_FACTOR: f64 = 2.5;
+
+/// Lowest ease factor, keeping hard puzzles from coming back every day forever.
+pub const MINIMUM_EASE_FACTOR: f64 = 1.3;
+
+/// When a completed puzzle should be practised again, using the SM-2 algorithm.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReviewSchedule {
+    pub(crate) repetitions: u32,
+    pub(crate) interval_days: u32,
+    pub(crate) ease_factor: f64,
+    pub(crate) last_quality: u8,
+    pub(crate) last_reviewed: DateTime<Utc>,
+    pub(crate) due: DateTime<Utc>,
+}
+
+impl ReviewSchedule {
+    fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            repetitions: 0,
+            interval_days: 0,
+            ease_factor: INITIAL_EASE_FACTOR,
+            last_quality: 0,
+            last_reviewed: timestamp,
+            due: timestamp,
+        }
+    }
+
+    /// Passes in a row that were recalled well enough to lengthen the interval.
+    pub fn get_repetitions(&self) -> u32 {
+        self.repetitions
+    }
+
+    pub fn get_interval_days(&self) -> u32 {
+        self.interval_days
+    }
+
+    pub fn get_ease_factor(&self) -> f64 {
+        self.ease_factor
+    }
+
+    /// Grade of the latest review from 0 to 5.
+    pub fn get_last_quality(&self) -> u8 {
+        self.last_quality
+    }
+
+    pub fn get_last_reviewed(&self) -> &DateTime<Utc> {
+        &self.last_reviewed
+    }
+
+    pub fn get_due(&self) -> &DateTime<Utc> {
+        &self.due
+    }
+
+    pub fn is_due(&self, timestamp: DateTime<Utc>) -> bool {
+        self.due <= timestamp
+    }
+
+    /// Schedules the next review from a graded review finished at `timestamp`.
+    fn record_review(&mut self, timestamp: DateTime<Utc>, quality: u8) {
+        let quality = quality.min(5);
+        if quality >= 3 {
+            self.interval_days = match self.repetitions {
+                0 => 1,
+                1 => 6,
+                _ => (f64::from(self.interval_days) * self.ease_factor).round() as u32,
+            };
+            self.repetitions += 1;
+        } else {
+            self.repetitions = 0;
+            self.interval_days = 1;
+        }
+
+        let difference = f64::from(5 - quality);
+        self.ease_factor = (self.ease_factor + 0.1 - difference * (0.08 + difference * 0.02))
+            .max(MINIMUM_EASE_FACTOR);
+        self.last_quality = quality;
+        self.last_reviewed = timestamp;
+        self.due = timestamp + Duration::days(i64::from(self.interval_days));
+    }
+}
+
+/// Grades a finished timed run from 0 to 5 for the review schedule.
+///
+/// Each failed check costs a point, up to three, and taking more than twice the previous
+/// personal best costs another.
+pub fn get_review_quality(run: &TimedRun, previous_best_seconds: Option<u64>) -> u8 {
+    let mut quality = 5 - run.failed_checks.min(3) as u8;
+    if previous_best_seconds.is_some_and(|best| run.active_seconds > best * 2) {
+        quality = quality.saturating_sub(1);
+    }
+    quality
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckAttempt {
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) result: CheckResult,
+    #[serde(default)]
+    pub(crate) stage: usize,
+}
+
+impl CheckAttempt {
+    pub fn get_timestamp(&self) -> &DateTime<Utc> {
+        &self.timestamp
+    }
+
+    pub fn get_result(&self) -> CheckResult {
+        self.result
+    }
+
+    /// The stage of a multi-stage puzzle the check was run against, counting from 0.
+    pub fn get_stage(&self) -> usize {
+        self.stage
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PuzzleProgress {
+    pub(crate) generated: Option<DateTime<Utc>>,
+    pub(crate) attempts: Vec<CheckAttempt>,
+    pub(crate) first_pass: Option<DateTime<Utc>>,
+    pub(crate) latest_pass: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub(crate) hints_revealed: usize,
+    #[serde(default)]
+    pub(crate) current_run: Option<TimedRun>,
+    #[serde(default)]
+    pub(crate) runs: Vec<TimedRun>,
+    #[serde(default)]
+    pub(crate) redos: usize,
+    #[serde(default)]
+    pub(crate) review: Option<ReviewSchedule>,
+    #[serde(default)]
+    pub(crate) stage: usize,
+}
+
+impl PuzzleProgress {
+    pub fn get_generated(&self) -> Option<&DateTime<Utc>> {
+        self.generated.as_ref()
+    }
+
+    pub fn get_attempts(&self) -> &[CheckAttempt] {
+        &self.attempts
+    }
+
+    pub fn get_first_pass(&self) -> Option<&DateTime<Utc>> {
+        self.first_pass.as_ref()
+    }
+
+    pub fn get_latest_pass(&self) -> Option<&DateTime<Utc>> {
+        self.latest_pass.as_ref()
+    }
+
+    pub fn get_hints_revealed(&self) -> usize {
+        self.hints_revealed
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.first_pass.is_some()
+    }
+
+    /// The timed run started by the latest `generate`, until a check passes.
+    pub fn get_current_run(&self) -> Option<&TimedRun> {
+        self.current_run.as_ref()
+    }
+
+    /// Every finished timed run, oldest first.
+    pub fn get_runs(&self) -> &[TimedRun] {
+        &self.runs
+    }
+
+    /// The fastest finished timed run.
+    pub fn get_personal_best(&self) -> Option<&TimedRun> {
+        self.runs.iter().min_by_key(|run| run.active_seconds)
+    }
+
+    /// The review schedule, set once a timed run finishes.
+    pub fn get_review(&self) -> Option<&ReviewSchedule> {
+        self.review.as_ref()
+    }
+
+    /// The number of times the puzzle has been archived and started again.
+    pub fn get_redos(&self) -> usize {
+        self.redos
+    }
+
+    /// The stage of a multi-stage puzzle being worked on, counting from 0.
+    pub fn get_stage(&self) -> usize {
+        self.stage
+    }
+
+    /// Reveal the next hint, returning the number of hints now revealed.
+    pub fn reveal_hint(&mut self, hint_count: usize) -> usize {
+        self.hints_revealed = (self.hints_revealed + 1).min(hint_count);
+        self.hints_revealed
+    }
+
+    /// Records the puzzle being generated, starting a new timed run.
+    pub fn record_generated(&mut self, timestamp: DateTime<Utc>) {
+        self.generated = Some(timestamp);
+        self.current_run = Some(TimedRun::new(timestamp));
+        self.stage = 0;
+    }
+
+    /// Records the puzzle being archived and generated again, starting a new timed run.
+    pub fn record_redo(&mut self, timestamp: DateTime<Utc>) {
+        self.redos += 1;
+        self.record_generated(timestamp);
+    }
+
+    /// Records working on the puzzle, such as a check, a hint or a change seen by `watch`.
+    pub fn record_activity(&mut self, timestamp: DateTime<Utc>) {
+        if let Some(current_run) = &mut self.current_run {
+            current_run.record_activity(timestamp);
+        }
+    }
+
+    /// Records a check passing a stage of a multi-stage puzzle before the last, returning the
+    /// stage it unlocked.
+    ///
+    /// The puzzle only counts as completed, and the timed run only finishes, once a check passes
+    /// the last stage.
+    pub fn record_stage_passed(&mut self, timestamp: DateTime<Utc>) -> usize {
+        self.record_activity(timestamp);
+        self.attempts.push(CheckAttempt {
+            timestamp,
+            result: CheckResult::Passed,
+            stage: self.stage,
+        });
+        self.stage += 1;
+        self.stage
+    }
+
+    /// Sets the stage the working puzzle is at, such as when its starter files or a backup of it
+    /// are written again.
+    pub fn set_stage(&mut self, stage: usize) {
+        self.stage = stage;
+    }
+
+    /// Records a check, returning the timed run it finished when it passed.
+    ///
+    /// Finishing a timed run counts as a review, scheduling when the puzzle is due again.
+    pub fn record_attempt(
+        &mut self,
+        timestamp: DateTime<Utc>,
+        result: CheckResult,
+    ) -> Option<&TimedRun> {
+        self.record_activity(timestamp);
+        self.attempts.push(CheckAttempt {
+            timestamp,
+            result,
+            stage: self.stage,
+        });
+
+        if result != CheckResult::Passed {
+            if let Some(current_run) = &mut self.current_run {
+                current_run.failed_checks += 1;
+            }
+            return None;
+        }
+
+        if self.first_pass.is_none() {
+            self.first_pass = Some(timestamp);
+        }
+        self.latest_pass = Some(timestamp);
+
+        let mut run = self.current_run.take()?;
+        run.finished = Some(timestamp);
+
+        let previous_best_seconds = self.get_personal_best().map(TimedRun::get_active_seconds);
+        let quality = get_review_quality(&run, previous_best_seconds);
+        self.review
+            .get_or_insert_with(|| ReviewSchedule::new(timestamp))
+            .record_review(timestamp, quality);
+
+        self.runs.push(run);
+        self.runs.last()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    #[serde(with = "puzzle_map")]
+    pub(crate) puzzles: BTreeMap<u16, PuzzleProgress>,
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            serde_json::to_string_pretty(&self).unwrap_or_default()
+        )
+    }
+}
+
+impl Progress {
+    pub fn get_puzzles(&self) -> &BTreeMap<u16, PuzzleProgress> {
+        &self.puzzles
+    }
+
+    pub fn get_puzzle(&self, id: u16) -> Option<&PuzzleProgress> {
+        self.puzzles.get(&id)
+    }
+
+    pub fn get_mut_puzzle(&mut self, id: u16) -> &mut PuzzleProgress {
+        self.puzzles.entry(id).or_default()
+    }
+
+    /// The progress of a puzzle that has been generated, without adding one for any other puzzle.
+    pub fn get_mut_generated_puzzle(&mut self, id: u16) -> Option<&mut PuzzleProgress> {
+        self.puzzles
+            .get_mut(&id)
+            .filter(|puzzle_progress| puzzle_progress.generated.is_some())
+    }
+
+    pub fn is_completed(&self, id: u16) -> bool {
+        self.get_puzzle(id)
+            .map(PuzzleProgress::is_completed)
+            .unwrap_or_default()
+    }
+
+    /// IDs of the puzzles due for review at `timestamp`, the longest overdue first.
+    pub fn get_due_reviews(&self, timestamp: DateTime<Utc>) -> Vec<u16> {
+        let mut due_reviews = self
+            .puzzles
+            .iter()
+            .filter_map(|(id, puzzle_progress)| Some((*id, puzzle_progress.get_review()?)))
+            .filter(|(_, review)| review.is_due(timestamp))
+            .collect::<Vec<_>>();
+        due_reviews.sort_by_key(|(id, review)| (review.due, *id));
+        due_reviews.into_iter().map(|(id, _)| id).collect()
+    }
+
+    /// A puzzle is in progress once it has been generated or checked.
+    pub fn get_state(&self, id: u16) -> PuzzleState {
+        match self.get_puzzle(id) {
+            Some(puzzle_progress) if puzzle_progress.is_completed() => PuzzleState::Completed,
+            Some(puzzle_progress)
+                if puzzle_progress.generated.is_some() || !puzzle_progress.attempts.is_empty() =>
+            {
+                PuzzleState::InProgress
+            }
+            _ => PuzzleState::NotStarted,
+        }
+    }
+}
+
+// Integer map keys do not survive the buffering done by the internally tagged
+// `VersionedProgress`, so the puzzle IDs are stored as strings.
+mod puzzle_map {
+    use super::PuzzleProgress;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::BTreeMap;
+
+    pub fn serialize<S: Serializer>(
+        puzzles: &BTreeMap<u16, PuzzleProgress>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        puzzles
+            .iter()
+            .map(|(id, progress)| (id.to_string(), progress))
+            .collect::<BTreeMap<String, &PuzzleProgress>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<u16, PuzzleProgress>, D::Error> {
+        BTreeMap::<String, PuzzleProgress>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(id, progress)| match id.parse::<u16>() {
+                Ok(id) => Ok((id, progress)),
+                Err(_) => Err(D::Error::custom(format!("invalid puzzle ID `{}`", id))),
+            })
+            .collect()
+    }
+}
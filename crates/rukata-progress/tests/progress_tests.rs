@@ -0,0 +1,323 @@
+#[macro_use]
+extern crate lazy_static;
+
+#[cfg(test)]
+use pretty_assertions::{assert_eq, assert_ne};
+
+use camino::Utf8PathBuf;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use rukata_progress::versioned_progress::VersionedProgress;
+use rukata_progress::versions::v1::{
+    CheckResult, PuzzleState, IDLE_LIMIT, INITIAL_EASE_FACTOR, MINIMUM_EASE_FACTOR,
+};
+use rukata_progress::ProgressHandler;
+use std::{env, fs};
+
+lazy_static! {
+    static ref FILES_DIR: Utf8PathBuf = Utf8PathBuf::from_path_buf(env::current_dir().unwrap())
+        .unwrap()
+        .join("tests")
+        .join("files");
+}
+
+fn remove_file_if_exists(path: Utf8PathBuf) {
+    if path.exists() {
+        fs::remove_file(path).unwrap()
+    }
+}
+
+fn timestamp(minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 3, 1, 10, minute, 0).unwrap()
+}
+
+#[test]
+fn test_default() {
+    let progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    assert_eq!(
+        progress_handler.get_progress(),
+        VersionedProgress::default().get_progress()
+    )
+}
+
+#[test]
+fn test_display() {
+    let progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    assert_eq!(
+        format!("{}", progress_handler.get_progress()),
+        "{\n  \"puzzles\": {}\n}"
+    )
+}
+
+#[test]
+fn test_save() {
+    let path = FILES_DIR.join("save.json");
+    let _cleanup = scopeguard::guard(path.clone(), remove_file_if_exists);
+
+    let mut progress_handler = ProgressHandler::new(path.clone()).unwrap();
+    {
+        let progress = progress_handler.get_mut_progress();
+        progress.get_mut_puzzle(0).record_generated(timestamp(0));
+    }
+
+    progress_handler.save().unwrap();
+
+    let progress = progress_handler.get_progress();
+    let progress_handler_load = ProgressHandler::new(path).unwrap();
+    assert_eq!(progress, progress_handler_load.get_progress());
+    assert_ne!(progress, VersionedProgress::default().get_progress());
+}
+
+#[test]
+fn test_record_attempts() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let progress = progress_handler.get_mut_progress();
+
+    let puzzle_progress = progress.get_mut_puzzle(3);
+    puzzle_progress.record_generated(timestamp(0));
+    puzzle_progress.record_attempt(timestamp(1), CheckResult::Failed);
+    assert!(!puzzle_progress.is_completed());
+
+    puzzle_progress.record_attempt(timestamp(2), CheckResult::Passed);
+    puzzle_progress.record_attempt(timestamp(3), CheckResult::Failed);
+    puzzle_progress.record_attempt(timestamp(4), CheckResult::Passed);
+
+    assert_eq!(puzzle_progress.get_attempts().len(), 4);
+    assert_eq!(puzzle_progress.get_first_pass(), Some(&timestamp(2)));
+    assert_eq!(puzzle_progress.get_latest_pass(), Some(&timestamp(4)));
+    assert!(progress.is_completed(3));
+    assert!(!progress.is_completed(4));
+}
+
+#[test]
+fn test_state() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let progress = progress_handler.get_mut_progress();
+    assert_eq!(progress.get_state(0), PuzzleState::NotStarted);
+
+    // Only generated puzzles are handed out without being added.
+    assert_eq!(progress.get_mut_generated_puzzle(0), None);
+    assert_eq!(progress.get_puzzle(0), None);
+
+    // A puzzle that has not been generated or checked is not started.
+    progress.get_mut_puzzle(0).set_stage(0);
+    assert_eq!(progress.get_state(0), PuzzleState::NotStarted);
+    assert_eq!(progress.get_mut_generated_puzzle(0), None);
+
+    progress.get_mut_puzzle(0).record_generated(timestamp(0));
+    assert_eq!(progress.get_state(0), PuzzleState::InProgress);
+    assert!(progress.get_mut_generated_puzzle(0).is_some());
+
+    progress
+        .get_mut_puzzle(1)
+        .record_attempt(timestamp(1), CheckResult::Failed);
+    assert_eq!(progress.get_state(1), PuzzleState::InProgress);
+
+    progress
+        .get_mut_puzzle(0)
+        .record_attempt(timestamp(1), CheckResult::Passed);
+    assert_eq!(progress.get_state(0), PuzzleState::Completed);
+}
+
+#[test]
+fn test_timed_runs() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let puzzle_progress = progress_handler.get_mut_progress().get_mut_puzzle(0);
+
+    // Checks without a generate are not timed.
+    assert_eq!(
+        puzzle_progress.record_attempt(timestamp(1), CheckResult::Passed),
+        None
+    );
+
+    // The 40 minute gap only counts as the idle limit.
+    puzzle_progress.record_generated(timestamp(2));
+    puzzle_progress.record_activity(timestamp(7));
+    puzzle_progress.record_activity(timestamp(4));
+    puzzle_progress.record_attempt(timestamp(10), CheckResult::Failed);
+    let run = puzzle_progress
+        .record_attempt(timestamp(50), CheckResult::Passed)
+        .unwrap();
+    assert_eq!(run.get_active_seconds(), 8 * 60 + IDLE_LIMIT);
+    assert_eq!(run.get_finished(), Some(&timestamp(50)));
+    assert_eq!(puzzle_progress.get_current_run(), None);
+
+    // Later passes do not change the finished run.
+    assert_eq!(
+        puzzle_progress.record_attempt(timestamp(51), CheckResult::Passed),
+        None
+    );
+
+    puzzle_progress.record_generated(timestamp(52));
+    puzzle_progress.record_attempt(timestamp(55), CheckResult::Passed);
+    assert_eq!(puzzle_progress.get_runs().len(), 2);
+    assert_eq!(
+        puzzle_progress.get_personal_best().unwrap().get_started(),
+        &timestamp(52)
+    );
+}
+
+#[test]
+fn test_record_redo() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let puzzle_progress = progress_handler.get_mut_progress().get_mut_puzzle(0);
+    puzzle_progress.record_generated(timestamp(0));
+    puzzle_progress.record_attempt(timestamp(5), CheckResult::Passed);
+    assert_eq!(puzzle_progress.get_redos(), 0);
+
+    // A redo keeps the earlier attempts and starts a new timed run.
+    puzzle_progress.record_redo(timestamp(10));
+    assert_eq!(puzzle_progress.get_redos(), 1);
+    assert_eq!(puzzle_progress.get_attempts().len(), 1);
+    assert_eq!(puzzle_progress.get_generated(), Some(&timestamp(10)));
+    assert_eq!(
+        puzzle_progress.get_current_run().unwrap().get_started(),
+        &timestamp(10)
+    );
+}
+
+#[test]
+fn test_review_schedule() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let progress = progress_handler.get_mut_progress();
+
+    // A clean first pass is due again the next day.
+    let puzzle_progress = progress.get_mut_puzzle(0);
+    assert_eq!(puzzle_progress.get_review(), None);
+    puzzle_progress.record_generated(timestamp(0));
+    puzzle_progress.record_attempt(timestamp(5), CheckResult::Passed);
+    let review = puzzle_progress.get_review().unwrap().clone();
+    assert_eq!(review.get_repetitions(), 1);
+    assert_eq!(review.get_interval_days(), 1);
+    assert_eq!(review.get_last_quality(), 5);
+    assert_eq!(review.get_due(), &(timestamp(5) + Duration::days(1)));
+    assert!(review.get_ease_factor() > INITIAL_EASE_FACTOR);
+
+    // The second clean pass waits six days, then the interval grows by the ease factor.
+    let day = timestamp(5) + Duration::days(1);
+    puzzle_progress.record_redo(day);
+    puzzle_progress.record_attempt(day, CheckResult::Passed);
+    assert_eq!(puzzle_progress.get_review().unwrap().get_interval_days(), 6);
+
+    let day = day + Duration::days(6);
+    puzzle_progress.record_redo(day);
+    puzzle_progress.record_attempt(day, CheckResult::Passed);
+    assert_eq!(
+        puzzle_progress.get_review().unwrap().get_interval_days(),
+        16
+    );
+
+    // Three failed checks before the pass start the repetitions again.
+    let day = day + Duration::days(16);
+    puzzle_progress.record_redo(day);
+    for _ in 0..3 {
+        puzzle_progress.record_attempt(day, CheckResult::Failed);
+    }
+    let run = puzzle_progress
+        .record_attempt(day, CheckResult::Passed)
+        .unwrap();
+    assert_eq!(run.get_failed_checks(), 3);
+    let review = puzzle_progress.get_review().unwrap();
+    assert_eq!(review.get_last_quality(), 2);
+    assert_eq!(review.get_repetitions(), 0);
+    assert_eq!(review.get_interval_days(), 1);
+    assert!(review.get_ease_factor() >= MINIMUM_EASE_FACTOR);
+
+    // Only puzzles with a review at or before the time are due, the longest overdue first.
+    progress.get_mut_puzzle(1).record_generated(timestamp(0));
+    progress
+        .get_mut_puzzle(1)
+        .record_attempt(timestamp(1), CheckResult::Passed);
+    progress.get_mut_puzzle(2).record_generated(timestamp(0));
+    assert_eq!(progress.get_due_reviews(timestamp(0)), Vec::<u16>::new());
+    assert_eq!(
+        progress.get_due_reviews(day + Duration::days(1)),
+        vec![1, 0]
+    );
+}
+
+#[test]
+fn test_stages() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let progress = progress_handler.get_mut_progress();
+    let puzzle_progress = progress.get_mut_puzzle(0);
+    puzzle_progress.record_generated(timestamp(0));
+    assert_eq!(puzzle_progress.get_stage(), 0);
+
+    // Passing a stage before the last unlocks the next one without completing the puzzle.
+    assert_eq!(puzzle_progress.record_stage_passed(timestamp(5)), 1);
+    assert_eq!(puzzle_progress.record_stage_passed(timestamp(10)), 2);
+    assert!(puzzle_progress.get_current_run().is_some());
+    assert!(puzzle_progress.get_review().is_none());
+    assert!(!progress.is_completed(0));
+    assert_eq!(progress.get_state(0), PuzzleState::InProgress);
+
+    // The timed run covers every stage.
+    let puzzle_progress = progress.get_mut_puzzle(0);
+    let run = puzzle_progress
+        .record_attempt(timestamp(15), CheckResult::Passed)
+        .unwrap();
+    assert_eq!(run.get_active_seconds(), 15 * 60);
+    assert_eq!(puzzle_progress.get_stage(), 2);
+
+    // Every stage passed is recorded as a check against that stage.
+    let stages: Vec<(usize, CheckResult)> = puzzle_progress
+        .get_attempts()
+        .iter()
+        .map(|attempt| (attempt.get_stage(), attempt.get_result()))
+        .collect();
+    assert_eq!(
+        stages,
+        vec![
+            (0, CheckResult::Passed),
+            (1, CheckResult::Passed),
+            (2, CheckResult::Passed)
+        ]
+    );
+    assert_eq!(puzzle_progress.get_first_pass(), Some(&timestamp(15)));
+
+    // Starting again goes back to the first stage, restoring a backup goes to the stage it was at.
+    puzzle_progress.record_redo(timestamp(20));
+    assert_eq!(puzzle_progress.get_stage(), 0);
+    puzzle_progress.set_stage(1);
+    assert_eq!(puzzle_progress.get_stage(), 1);
+}
+
+#[test]
+fn test_reveal_hint() {
+    let mut progress_handler = ProgressHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let puzzle_progress = progress_handler.get_mut_progress().get_mut_puzzle(0);
+    assert_eq!(puzzle_progress.get_hints_revealed(), 0);
+    assert_eq!(puzzle_progress.reveal_hint(2), 1);
+    assert_eq!(puzzle_progress.reveal_hint(2), 2);
+    assert_eq!(puzzle_progress.reveal_hint(2), 2);
+}
+
+#[test]
+fn test_missing_version() {
+    let path = FILES_DIR.join("missing_version.json");
+    assert_eq!(
+        ProgressHandler::new(path.clone()).err(),
+        Some(format!(
+            "Failed to read progress `{}` with error: missing field `version` at line 3 column 1",
+            path
+        ))
+    );
+}
+
+#[test]
+fn test_valid_basic() {
+    let path = FILES_DIR.join("valid_basic.json");
+    assert_eq!(ProgressHandler::new(path.clone()).err(), None);
+    let progress_handler = ProgressHandler::new(path).unwrap();
+    let puzzle_progress = progress_handler.get_progress().get_puzzle(0).unwrap();
+    assert_eq!(puzzle_progress.get_generated(), Some(&timestamp(0)));
+    assert_eq!(
+        puzzle_progress
+            .get_attempts()
+            .iter()
+            .map(|attempt| attempt.get_result())
+            .collect::<Vec<_>>(),
+        vec![CheckResult::Failed, CheckResult::Passed]
+    );
+    assert!(puzzle_progress.is_completed());
+}
@@ -1,7 +1,9 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use glob::glob;
 use phf_codegen::Map;
+use regex::Regex;
 use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs::File;
 use std::io::{BufReader, BufWriter, Read, Write};
@@ -19,6 +21,120 @@ enum RukataPuzzleDifficulty {
     None,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum RukataLintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RukataPuzzleKind {
+    #[default]
+    Standard,
+    CompileFail,
+    WriteTests,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RukataCompileFailSnippet {
+    path: String,
+    code: String,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RukataMutant {
+    name: String,
+    description: String,
+    files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RukataStage {
+    name: String,
+    title: String,
+    description: String,
+    starter: Vec<String>,
+    #[serde(default)]
+    solution: Vec<String>,
+}
+
+impl RukataStage {
+    fn get_read_only_files(&self) -> Vec<String> {
+        self.starter
+            .iter()
+            .filter(|path| !self.solution.contains(path))
+            .cloned()
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum RukataCheckStep {
+    Test {
+        #[serde(default)]
+        filter: Option<String>,
+    },
+    Run {
+        #[serde(default)]
+        bin: Option<String>,
+        #[serde(default)]
+        arguments: Vec<String>,
+        #[serde(default)]
+        stdin: String,
+        #[serde(default)]
+        stdout: Option<String>,
+        #[serde(default)]
+        stderr: Option<String>,
+    },
+    DocTest,
+    Build {
+        #[serde(default)]
+        features: Vec<String>,
+    },
+    Cargo {
+        arguments: Vec<String>,
+    },
+}
+
+impl RukataCheckStep {
+    fn write_to_file(&self, writer: &mut BufWriter<File>) {
+        match self {
+            RukataCheckStep::Test { filter } => {
+                write!(writer, "CheckStep::Test {{ filter: {:?} }}", filter).unwrap()
+            }
+            RukataCheckStep::Run {
+                bin,
+                arguments,
+                stdin,
+                stdout,
+                stderr,
+            } => write!(
+                writer,
+                "CheckStep::Run {{ bin: {:?}, arguments: &{:?}, stdin: {:?}, stdout: {:?}, stderr: {:?} }}",
+                bin, arguments, stdin, stdout, stderr
+            )
+            .unwrap(),
+            RukataCheckStep::DocTest => write!(writer, "CheckStep::DocTest").unwrap(),
+            RukataCheckStep::Build { features } => {
+                write!(writer, "CheckStep::Build {{ features: &{:?} }}", features).unwrap()
+            }
+            RukataCheckStep::Cargo { arguments } => {
+                write!(writer, "CheckStep::Cargo {{ arguments: &{:?} }}", arguments).unwrap()
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct RukataPuzzleConfig {
     title: String,
@@ -29,6 +145,30 @@ struct RukataPuzzleConfig {
     difficulty: RukataPuzzleDifficulty,
     categories: Vec<String>,
     libraries: Vec<String>,
+    #[serde(default)]
+    hints: Vec<String>,
+    #[serde(default)]
+    error_explanations: BTreeMap<String, String>,
+    #[serde(default)]
+    timeout: Option<u64>,
+    #[serde(default)]
+    clippy_lints: BTreeMap<String, RukataLintLevel>,
+    #[serde(default)]
+    check_formatting: bool,
+    #[serde(default)]
+    hidden_tests: Vec<String>,
+    #[serde(default)]
+    check_steps: Vec<RukataCheckStep>,
+    #[serde(default)]
+    kind: RukataPuzzleKind,
+    #[serde(default)]
+    compile_fail: Vec<RukataCompileFailSnippet>,
+    #[serde(default)]
+    mutants: Vec<RukataMutant>,
+    #[serde(default)]
+    requires: Vec<u16>,
+    #[serde(default)]
+    stages: Vec<RukataStage>,
 }
 
 impl RukataPuzzleConfig {
@@ -45,6 +185,197 @@ impl RukataPuzzleConfig {
             .collect()
     }
 
+    fn check_hidden_tests(&self) {
+        for path in &self.hidden_tests {
+            if self.starter.contains(path) || self.solution.contains(path) {
+                panic!(
+                    "Hidden test `{}` of puzzle {} would replace a starter or solution file",
+                    path, self.id
+                );
+            }
+
+            let relative_path = Utf8PathBuf::from(path);
+            if relative_path.parent().map(Utf8Path::as_str) != Some("tests")
+                || relative_path.extension() != Some("rs")
+            {
+                panic!(
+                    "Hidden test `{}` of puzzle {} must be a `tests/*.rs` file",
+                    path, self.id
+                );
+            }
+        }
+    }
+
+    fn check_check_steps(&self) {
+        for step in &self.check_steps {
+            if let RukataCheckStep::Cargo { arguments } = step {
+                if arguments.is_empty() {
+                    panic!(
+                        "Cargo check step of puzzle {} does not have a subcommand",
+                        self.id
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_compile_fail(&self) {
+        match self.kind {
+            RukataPuzzleKind::Standard if !self.compile_fail.is_empty() => panic!(
+                "Puzzle {} has compile-fail snippets but is not a `compile_fail` puzzle",
+                self.id
+            ),
+            RukataPuzzleKind::CompileFail if self.compile_fail.is_empty() => panic!(
+                "Compile-fail puzzle {} does not have any compile-fail snippets",
+                self.id
+            ),
+            _ => {}
+        }
+
+        for snippet in &self.compile_fail {
+            // Snippets are kept out of the folders cargo builds on its own.
+            let relative_path = Utf8PathBuf::from(&snippet.path);
+            if relative_path.parent().map(Utf8Path::as_str) != Some("snippets")
+                || relative_path.extension() != Some("rs")
+            {
+                panic!(
+                    "Compile-fail snippet `{}` of puzzle {} must be a `snippets/*.rs` file",
+                    snippet.path, self.id
+                );
+            }
+
+            if !self.starter.contains(&snippet.path) {
+                panic!(
+                    "Compile-fail snippet `{}` of puzzle {} is not a starter file",
+                    snippet.path, self.id
+                );
+            }
+
+            let code = snippet.code.strip_prefix('E').unwrap_or_default();
+            if code.len() != 4 || !code.chars().all(|character| character.is_ascii_digit()) {
+                panic!(
+                    "Compile-fail snippet `{}` of puzzle {} has an invalid error code `{}`",
+                    snippet.path, self.id, snippet.code
+                );
+            }
+
+            if let Some(message) = &snippet.message {
+                if let Err(e) = Regex::new(message) {
+                    panic!(
+                        "Compile-fail snippet `{}` of puzzle {} has an invalid message pattern: {}",
+                        snippet.path, self.id, e
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_mutants(&self) {
+        match self.kind {
+            RukataPuzzleKind::WriteTests if self.mutants.is_empty() => panic!(
+                "Write-the-tests puzzle {} does not have any mutants",
+                self.id
+            ),
+            RukataPuzzleKind::Standard | RukataPuzzleKind::CompileFail
+                if !self.mutants.is_empty() =>
+            {
+                panic!(
+                    "Puzzle {} has mutants but is not a `write_tests` puzzle",
+                    self.id
+                )
+            }
+            _ => {}
+        }
+
+        let read_only_files = self.get_read_only_files();
+        for (index, mutant) in self.mutants.iter().enumerate() {
+            if mutant.files.is_empty() {
+                panic!(
+                    "Mutant `{}` of puzzle {} does not change any files",
+                    mutant.name, self.id
+                );
+            }
+
+            if self.mutants[..index]
+                .iter()
+                .any(|other| other.name == mutant.name)
+            {
+                panic!(
+                    "Mutant `{}` of puzzle {} is not unique",
+                    mutant.name, self.id
+                );
+            }
+
+            // The given implementation is read-only so the user can not change what is mutated.
+            for path in &mutant.files {
+                if !read_only_files.contains(path) {
+                    panic!(
+                        "Mutant `{}` of puzzle {} replaces `{}` which is not a read-only starter file",
+                        mutant.name, self.id, path
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_stages(&self) {
+        if self.kind != RukataPuzzleKind::Standard && !self.stages.is_empty() {
+            panic!(
+                "Puzzle {} has stages but is not a `standard` puzzle",
+                self.id
+            );
+        }
+
+        // Files the user writes in a stage are never replaced by a later stage.
+        let mut user_files: Vec<&String> = self.solution.iter().collect();
+        for (index, stage) in self.stages.iter().enumerate() {
+            if stage.name.is_empty()
+                || !stage.name.chars().all(|character| {
+                    character.is_ascii_lowercase() || character.is_ascii_digit() || character == '_'
+                })
+            {
+                panic!(
+                    "Stage `{}` of puzzle {} must only use lowercase letters, digits and `_` in its name",
+                    stage.name, self.id
+                );
+            }
+
+            if self.stages[..index]
+                .iter()
+                .any(|other| other.name == stage.name)
+            {
+                panic!("Stage `{}` of puzzle {} is not unique", stage.name, self.id);
+            }
+
+            if stage.starter.is_empty() {
+                panic!(
+                    "Stage `{}` of puzzle {} does not add any starter files",
+                    stage.name, self.id
+                );
+            }
+
+            for path in stage.starter.iter().chain(stage.solution.iter()) {
+                if self.hidden_tests.contains(path) {
+                    panic!(
+                        "Stage `{}` of puzzle {} would replace the hidden test `{}`",
+                        stage.name, self.id, path
+                    );
+                }
+            }
+
+            for path in &stage.starter {
+                if user_files.contains(&path) {
+                    panic!(
+                        "Stage `{}` of puzzle {} would replace `{}` which the user has already written",
+                        stage.name, self.id, path
+                    );
+                }
+            }
+
+            user_files.extend(stage.solution.iter());
+        }
+    }
+
     fn from(path: &Utf8PathBuf) -> serde_json::Result<RukataPuzzleConfig> {
         let file =
             File::open(path).unwrap_or_else(|_| panic!("Failed to open config file: {}", path));
@@ -52,6 +383,26 @@ impl RukataPuzzleConfig {
     }
 }
 
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RukataTrack {
+    id: String,
+    title: String,
+    description: String,
+    puzzles: Vec<u16>,
+}
+
+impl RukataTrack {
+    fn write_to_file(&self, writer: &mut BufWriter<File>) {
+        write!(
+            writer,
+            "PuzzleTrack {{ id: {:?}, title: {:?}, description: {:?}, puzzles: &{:?} }}",
+            self.id, self.title, self.description, self.puzzles
+        )
+        .unwrap();
+    }
+}
+
 struct FileData {
     relative_path: Utf8PathBuf,
     data_uncompressed: Vec<u8>,
@@ -77,7 +428,7 @@ impl StringData {
     fn write_to_file(&self, writer: &mut BufWriter<File>) {
         write!(
             writer,
-            "&PuzzleFileData {{ relative_path: \"{}\", data: &PuzzleFileEnum::String(&{:?}) }}",
+            "&PuzzleFileData {{ relative_path: \"{}\", data: &PuzzleFileEnum::String({:?}) }}",
             self.relative_path, self.data_string
         )
         .unwrap();
@@ -95,6 +446,33 @@ struct PuzzleData {
     difficulty: RukataPuzzleDifficulty,
     categories: Vec<String>,
     libraries: Vec<String>,
+    hints: Vec<String>,
+    error_explanations: BTreeMap<String, String>,
+    timeout: Option<u64>,
+    clippy_lints: BTreeMap<String, RukataLintLevel>,
+    check_formatting: bool,
+    hidden_tests: Vec<FileData>,
+    check_steps: Vec<RukataCheckStep>,
+    kind: RukataPuzzleKind,
+    compile_fail: Vec<RukataCompileFailSnippet>,
+    mutants: Vec<MutantData>,
+    requires: Vec<u16>,
+    stages: Vec<StageData>,
+}
+
+struct StageData {
+    name: String,
+    title: String,
+    description: String,
+    starter: Vec<FileData>,
+    solution: Vec<FileData>,
+    read_only_file_paths: Vec<String>,
+}
+
+struct MutantData {
+    name: String,
+    description: String,
+    files: Vec<FileData>,
 }
 
 impl PuzzleData {
@@ -127,7 +505,7 @@ impl PuzzleData {
         Self::write_file_vector(writer, &self.readme_files);
         write!(writer, "    read_only_file_paths: &[").unwrap();
         for read_only_file_path in &self.read_only_file_paths {
-            write!(writer, "&\"{}\",", read_only_file_path).unwrap();
+            write!(writer, "\"{}\",", read_only_file_path).unwrap();
         }
         writeln!(writer, "],").unwrap();
         writeln!(
@@ -138,12 +516,80 @@ impl PuzzleData {
         .unwrap();
         write!(writer, "    categories: &[").unwrap();
         for category in &self.categories {
-            write!(writer, "&\"{}\",", category).unwrap();
+            write!(writer, "\"{}\",", category).unwrap();
         }
         writeln!(writer, "],").unwrap();
         write!(writer, "    libraries: &[").unwrap();
         for library in &self.libraries {
-            write!(writer, "&\"{}\",", library).unwrap();
+            write!(writer, "\"{}\",", library).unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        write!(writer, "    hints: &[").unwrap();
+        for hint in &self.hints {
+            write!(writer, "{:?},", hint).unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        write!(writer, "    error_explanations: &[").unwrap();
+        for (code, explanation) in &self.error_explanations {
+            write!(writer, "({:?}, {:?}),", code, explanation).unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        writeln!(writer, "    timeout: {:?},", self.timeout).unwrap();
+        write!(writer, "    clippy_lints: &[").unwrap();
+        for (lint, level) in &self.clippy_lints {
+            write!(writer, "({:?}, LintLevel::{:?}),", lint, level).unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        writeln!(writer, "    check_formatting: {:?},", self.check_formatting).unwrap();
+        write!(writer, "    hidden_tests: ").unwrap();
+        Self::write_file_vector(writer, &self.hidden_tests);
+        write!(writer, "    check_steps: &[").unwrap();
+        for step in &self.check_steps {
+            step.write_to_file(writer);
+            write!(writer, ",").unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        writeln!(writer, "    kind: &PuzzleKind::{:?},", self.kind).unwrap();
+        write!(writer, "    compile_fail: &[").unwrap();
+        for snippet in &self.compile_fail {
+            write!(
+                writer,
+                "CompileFailSnippet {{ path: {:?}, code: {:?}, message: {:?} }},",
+                snippet.path, snippet.code, snippet.message
+            )
+            .unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        write!(writer, "    mutants: &[").unwrap();
+        for mutant in &self.mutants {
+            write!(
+                writer,
+                "PuzzleMutant {{ name: {:?}, description: {:?}, files: ",
+                mutant.name, mutant.description
+            )
+            .unwrap();
+            Self::write_file_vector(writer, &mutant.files);
+            write!(writer, "}},").unwrap();
+        }
+        writeln!(writer, "],").unwrap();
+        writeln!(writer, "    requires: &{:?},", self.requires).unwrap();
+        write!(writer, "    stages: &[").unwrap();
+        for stage in &self.stages {
+            write!(
+                writer,
+                "PuzzleStage {{ name: {:?}, title: {:?}, description: {:?}, starter: ",
+                stage.name, stage.title, stage.description
+            )
+            .unwrap();
+            Self::write_file_vector(writer, &stage.starter);
+            write!(writer, "solution: ").unwrap();
+            Self::write_file_vector(writer, &stage.solution);
+            write!(
+                writer,
+                "read_only_file_paths: &{:?} }},",
+                stage.read_only_file_paths
+            )
+            .unwrap();
         }
         writeln!(writer, "],").unwrap();
         writeln!(writer, "}};").unwrap();
@@ -201,6 +647,11 @@ fn get_readme_data(path: &Utf8PathBuf, config: &RukataPuzzleConfig) -> StringDat
 fn get_puzzle_data(puzzle_config_path: &Utf8PathBuf) -> PuzzleData {
     // Read the config.
     let config = RukataPuzzleConfig::from(puzzle_config_path).unwrap();
+    config.check_hidden_tests();
+    config.check_check_steps();
+    config.check_compile_fail();
+    config.check_mutants();
+    config.check_stages();
 
     // Get the puzzle folder.
     let puzzle_folder_path = puzzle_config_path
@@ -220,6 +671,103 @@ fn get_puzzle_data(puzzle_config_path: &Utf8PathBuf) -> PuzzleData {
         difficulty: config.difficulty,
         categories: config.categories,
         libraries: config.libraries,
+        hints: config.hints,
+        error_explanations: config.error_explanations,
+        timeout: config.timeout,
+        clippy_lints: config.clippy_lints,
+        check_formatting: config.check_formatting,
+        hidden_tests: get_file_list(&puzzle_folder_path.join("hidden"), &config.hidden_tests),
+        check_steps: config.check_steps,
+        kind: config.kind,
+        compile_fail: config.compile_fail,
+        mutants: config
+            .mutants
+            .iter()
+            .map(|mutant| MutantData {
+                name: mutant.name.to_string(),
+                description: mutant.description.to_string(),
+                files: get_file_list(
+                    &puzzle_folder_path.join("mutants").join(&mutant.name),
+                    &mutant.files,
+                ),
+            })
+            .collect(),
+        requires: config.requires,
+        stages: config
+            .stages
+            .iter()
+            .map(|stage| {
+                let stage_path = puzzle_folder_path.join("stages").join(&stage.name);
+                StageData {
+                    name: stage.name.to_string(),
+                    title: stage.title.to_string(),
+                    description: stage.description.to_string(),
+                    starter: get_file_list(&stage_path.join("starter"), &stage.starter),
+                    solution: get_file_list(&stage_path.join("solution"), &stage.solution),
+                    read_only_file_paths: stage.get_read_only_files(),
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Panics when a puzzle requires itself, an unknown puzzle or is part of a cycle of requirements.
+fn check_requires(puzzles: &BTreeMap<u16, PuzzleData>) {
+    for puzzle_data in puzzles.values() {
+        let mut seen = BTreeSet::new();
+        for required_id in &puzzle_data.requires {
+            if *required_id == puzzle_data.id {
+                panic!("Puzzle {} requires itself", puzzle_data.id);
+            }
+
+            if !puzzles.contains_key(required_id) {
+                panic!(
+                    "Puzzle {} requires puzzle {} which does not exist",
+                    puzzle_data.id, required_id
+                );
+            }
+
+            if !seen.insert(*required_id) {
+                panic!(
+                    "Puzzle {} requires puzzle {} more than once",
+                    puzzle_data.id, required_id
+                );
+            }
+        }
+    }
+
+    // Depth first search, a puzzle found again while its requirements are still being visited
+    // closes a cycle.
+    fn visit(
+        id: u16,
+        puzzles: &BTreeMap<u16, PuzzleData>,
+        visiting: &mut Vec<u16>,
+        visited: &mut BTreeSet<u16>,
+    ) {
+        if visited.contains(&id) {
+            return;
+        }
+
+        if let Some(index) = visiting.iter().position(|visiting_id| *visiting_id == id) {
+            let cycle = visiting[index..]
+                .iter()
+                .chain([id].iter())
+                .map(u16::to_string)
+                .collect::<Vec<String>>();
+            panic!("Puzzle requirements form a cycle: {}", cycle.join(" -> "));
+        }
+
+        visiting.push(id);
+        for required_id in &puzzles[&id].requires {
+            visit(*required_id, puzzles, visiting, visited);
+        }
+        visiting.pop();
+        visited.insert(id);
+    }
+
+    let mut visited = BTreeSet::new();
+    for id in puzzles.keys() {
+        visit(*id, puzzles, &mut Vec::new(), &mut visited);
     }
 }
 
@@ -257,12 +805,80 @@ fn write_rust_file(output_directory: &Utf8Path, puzzle_data: PuzzleData, rust_na
     }
 }
 
+/// Reads the tracks file, panicking when a track is not valid for the puzzles.
+fn get_tracks(tracks_path: &Utf8Path, puzzles: &BTreeMap<u16, PuzzleData>) -> Vec<RukataTrack> {
+    if !tracks_path.exists() {
+        return Vec::new();
+    }
+
+    let file = File::open(tracks_path)
+        .unwrap_or_else(|_| panic!("Failed to open tracks file: {}", tracks_path));
+    let tracks: Vec<RukataTrack> = serde_json::from_reader(file).unwrap_or_else(|e| {
+        panic!(
+            "Failed to read tracks file `{}` with error: {}",
+            tracks_path, e
+        )
+    });
+
+    for (index, track) in tracks.iter().enumerate() {
+        // Track IDs are typed on the command line and used as page names.
+        if track.id.is_empty()
+            || !track.id.chars().all(|character| {
+                character.is_ascii_lowercase() || character.is_ascii_digit() || character == '-'
+            })
+        {
+            panic!(
+                "Track `{}` must only use lowercase letters, digits and `-` in its ID",
+                track.id
+            );
+        }
+
+        if tracks[..index].iter().any(|other| other.id == track.id) {
+            panic!("Track `{}` is not unique", track.id);
+        }
+
+        if track.puzzles.is_empty() {
+            panic!("Track `{}` does not have any puzzles", track.id);
+        }
+
+        for (position, puzzle_id) in track.puzzles.iter().enumerate() {
+            let Some(puzzle_data) = puzzles.get(puzzle_id) else {
+                panic!(
+                    "Track `{}` has puzzle {} which does not exist",
+                    track.id, puzzle_id
+                );
+            };
+
+            if track.puzzles[..position].contains(puzzle_id) {
+                panic!(
+                    "Track `{}` has puzzle {} more than once",
+                    track.id, puzzle_id
+                );
+            }
+
+            // Puzzles required by another puzzle in the track have to come first.
+            for required_id in &puzzle_data.requires {
+                if track.puzzles[position..].contains(required_id) {
+                    panic!(
+                        "Track `{}` has puzzle {} before puzzle {} which it requires",
+                        track.id, puzzle_id, required_id
+                    );
+                }
+            }
+        }
+    }
+
+    tracks
+}
+
 fn generate_puzzle_map(puzzles_directory: &Utf8Path, output_directory: &Utf8Path) {
     // Generate some containers to hold data for the main files references.
     let mut filenames: Vec<String> = Vec::new();
     let mut map = Map::new();
 
-    // Find the `puzzle-config.json` files and generate a rust file with the data.
+    // Find the `puzzle-config.json` files and read the data.
+    let mut puzzles = BTreeMap::new();
+    let mut puzzle_folders: BTreeMap<u16, Utf8PathBuf> = BTreeMap::new();
     for path in glob(&format!("{}/**/puzzle-config.json", puzzles_directory))
         .expect("Failed to read glob pattern.")
         .flatten()
@@ -271,12 +887,32 @@ fn generate_puzzle_map(puzzles_directory: &Utf8Path, output_directory: &Utf8Path
         let puzzle_config_path = Utf8PathBuf::from_path_buf(path).expect("Invalid UTF-8 path.");
         let puzzle_data = get_puzzle_data(&puzzle_config_path);
 
-        // Generate a rust safe name from the folder.
-        // This will have to be watched...
         let puzzle_folder_path = puzzle_config_path
             .parent()
-            .expect("Puzzle folder doesn't exist?");
-        let puzzle_folder_name = puzzle_folder_path
+            .expect("Puzzle folder doesn't exist?")
+            .to_path_buf();
+
+        // A puzzle sharing the ID of another would replace it in the map.
+        if let Some(other_folder_path) = puzzle_folders.get(&puzzle_data.id) {
+            panic!(
+                "Puzzle ID {} is used by both `{}` and `{}`",
+                puzzle_data.id, other_folder_path, puzzle_folder_path
+            );
+        }
+
+        puzzle_folders.insert(puzzle_data.id, puzzle_folder_path);
+        puzzles.insert(puzzle_data.id, puzzle_data);
+    }
+
+    // Requirements and tracks can only be checked once every puzzle is known.
+    check_requires(&puzzles);
+    let tracks = get_tracks(&puzzles_directory.join("tracks.json"), &puzzles);
+
+    // Generate a rust file with the data of each puzzle.
+    for (id, puzzle_data) in puzzles {
+        // Generate a rust safe name from the folder.
+        // This will have to be watched...
+        let puzzle_folder_name = puzzle_folders[&id]
             .file_name()
             .expect("Puzzle folder doesn't have a name?");
         let rust_safe_puzzle_name = puzzle_folder_name.replace('-', "_");
@@ -316,6 +952,15 @@ fn generate_puzzle_map(puzzles_directory: &Utf8Path, output_directory: &Utf8Path
     )
     .unwrap();
     writeln!(&mut codegen_writer, ";").unwrap();
+
+    // Write the tracks in the order of the tracks file.
+    writeln!(&mut codegen_writer).unwrap();
+    write!(&mut codegen_writer, "static TRACKS: &[PuzzleTrack] = &[").unwrap();
+    for track in &tracks {
+        track.write_to_file(&mut codegen_writer);
+        write!(&mut codegen_writer, ",").unwrap();
+    }
+    writeln!(&mut codegen_writer, "];").unwrap();
 }
 
 fn main() {
@@ -0,0 +1,10 @@
+/// A token that can only be spent once.
+#[derive(Debug)]
+pub struct Token {
+    pub id: u32,
+}
+
+/// Spends the token, returning its ID.
+pub fn spend(token: Token) -> u32 {
+    token.id
+}
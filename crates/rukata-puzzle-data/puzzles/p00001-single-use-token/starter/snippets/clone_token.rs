@@ -0,0 +1,8 @@
+use p00001_single_use_token::{spend, Token};
+
+fn main() {
+    let token = Token { id: 1 };
+    let copy = token.clone();
+    spend(token);
+    spend(copy);
+}
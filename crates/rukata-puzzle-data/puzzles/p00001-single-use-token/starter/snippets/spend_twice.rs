@@ -0,0 +1,7 @@
+use p00001_single_use_token::{spend, Token};
+
+fn main() {
+    let token = Token { id: 1 };
+    spend(token);
+    spend(token);
+}
@@ -0,0 +1,7 @@
+use p00001_single_use_token::{spend, Token};
+
+#[test]
+fn spend_test() {
+    let token = Token { id: 7 };
+    assert_eq!(spend(token), 7);
+}
@@ -0,0 +1,8 @@
+/// Restricts `value` to the range `min..=max`.
+pub fn clamp(value: i32, _min: i32, max: i32) -> i32 {
+    if value > max {
+        max
+    } else {
+        value
+    }
+}
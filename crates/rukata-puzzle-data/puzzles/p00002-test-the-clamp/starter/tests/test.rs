@@ -0,0 +1,6 @@
+use p00002_test_the_clamp::clamp;
+
+#[test]
+fn within_range_test() {
+    assert_eq!(clamp(5, 0, 10), 5);
+}
@@ -0,0 +1,28 @@
+use p00003_shape_areas::{total_area, Rectangle, Square};
+
+#[test]
+fn squares_test() {
+    let squares = [Square { side: 1.0 }, Square { side: 2.0 }];
+    assert_eq!(total_area(&squares), 5.0);
+}
+
+#[test]
+fn rectangles_test() {
+    let rectangles = [
+        Rectangle {
+            width: 1.0,
+            height: 2.0,
+        },
+        Rectangle {
+            width: 3.0,
+            height: 4.0,
+        },
+    ];
+    assert_eq!(total_area(&rectangles), 14.0);
+}
+
+#[test]
+fn empty_test() {
+    let squares: [Square; 0] = [];
+    assert_eq!(total_area(&squares), 0.0);
+}
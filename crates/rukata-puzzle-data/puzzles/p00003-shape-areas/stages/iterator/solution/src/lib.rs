@@ -0,0 +1,32 @@
+mod squares;
+
+pub use squares::Squares;
+
+pub trait Area {
+    fn area(&self) -> f64;
+}
+
+pub struct Square {
+    pub side: f64,
+}
+
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Area for Square {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+impl Area for Rectangle {
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+pub fn total_area<T: Area>(shapes: &[T]) -> f64 {
+    shapes.iter().map(Area::area).sum()
+}
@@ -0,0 +1,25 @@
+use crate::Square;
+
+pub struct Squares {
+    side: f64,
+    limit: f64,
+}
+
+impl Squares {
+    pub fn new(limit: f64) -> Self {
+        Squares { side: 0.0, limit }
+    }
+}
+
+impl Iterator for Squares {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.side >= self.limit {
+            return None;
+        }
+
+        self.side += 1.0;
+        Some(Square { side: self.side })
+    }
+}
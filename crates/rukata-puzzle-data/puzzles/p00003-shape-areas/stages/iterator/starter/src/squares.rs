@@ -0,0 +1,20 @@
+use crate::Square;
+
+pub struct Squares {
+    side: f64,
+    limit: f64,
+}
+
+impl Squares {
+    pub fn new(limit: f64) -> Self {
+        todo!();
+    }
+}
+
+impl Iterator for Squares {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        todo!();
+    }
+}
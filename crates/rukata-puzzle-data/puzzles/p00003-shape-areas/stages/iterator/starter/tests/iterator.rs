@@ -0,0 +1,24 @@
+use p00003_shape_areas::{total_area, Area, Squares};
+
+#[test]
+fn sides_test() {
+    let sides: Vec<f64> = Squares::new(3.0).map(|square| square.side).collect();
+    assert_eq!(sides, vec![1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn areas_test() {
+    let areas: Vec<f64> = Squares::new(3.0).map(|square| square.area()).collect();
+    assert_eq!(areas, vec![1.0, 4.0, 9.0]);
+}
+
+#[test]
+fn total_test() {
+    let squares: Vec<_> = Squares::new(4.0).collect();
+    assert_eq!(total_area(&squares), 30.0);
+}
+
+#[test]
+fn empty_test() {
+    assert_eq!(Squares::new(0.0).count(), 0);
+}
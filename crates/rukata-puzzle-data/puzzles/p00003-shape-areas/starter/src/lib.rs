@@ -0,0 +1,24 @@
+pub trait Area {
+    fn area(&self) -> f64;
+}
+
+pub struct Square {
+    pub side: f64,
+}
+
+pub struct Rectangle {
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Area for Square {
+    fn area(&self) -> f64 {
+        todo!();
+    }
+}
+
+impl Area for Rectangle {
+    fn area(&self) -> f64 {
+        todo!();
+    }
+}
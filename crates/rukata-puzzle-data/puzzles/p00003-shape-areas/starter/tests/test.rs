@@ -0,0 +1,16 @@
+use p00003_shape_areas::{Area, Rectangle, Square};
+
+#[test]
+fn square_test() {
+    let square = Square { side: 3.0 };
+    assert_eq!(square.area(), 9.0);
+}
+
+#[test]
+fn rectangle_test() {
+    let rectangle = Rectangle {
+        width: 2.0,
+        height: 5.0,
+    };
+    assert_eq!(rectangle.area(), 10.0);
+}
@@ -0,0 +1,7 @@
+pub fn sum_even_squares(numbers: &[i64]) -> i64 {
+    numbers
+        .iter()
+        .filter(|number| *number % 2 == 0)
+        .map(|number| number * number)
+        .sum()
+}
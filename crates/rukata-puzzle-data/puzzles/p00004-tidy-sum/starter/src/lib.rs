@@ -0,0 +1,7 @@
+pub fn sum_even_squares(numbers: &[i64]) -> i64 {
+    let mut total = 0;
+    for i in 0..numbers.len() {
+        if numbers[i] % 2 == 0 { total += numbers[i] * numbers[i]; }
+    }
+    return total;
+}
@@ -0,0 +1,16 @@
+use p00004_tidy_sum::sum_even_squares;
+
+#[test]
+fn empty_test() {
+    assert_eq!(sum_even_squares(&[]), 0);
+}
+
+#[test]
+fn mixed_test() {
+    assert_eq!(sum_even_squares(&[1, 2, 3, 4]), 20);
+}
+
+#[test]
+fn negative_test() {
+    assert_eq!(sum_even_squares(&[-2, -3]), 4);
+}
@@ -0,0 +1,11 @@
+use p00005_leap_years::is_leap_year;
+
+#[test]
+fn century_test() {
+    assert!(!is_leap_year(1900));
+}
+
+#[test]
+fn fourth_century_test() {
+    assert!(is_leap_year(2000));
+}
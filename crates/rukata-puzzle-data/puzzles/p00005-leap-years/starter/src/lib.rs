@@ -0,0 +1,3 @@
+pub fn is_leap_year(year: u32) -> bool {
+    todo!();
+}
@@ -0,0 +1,11 @@
+use p00005_leap_years::is_leap_year;
+
+#[test]
+fn leap_year_test() {
+    assert!(is_leap_year(2024));
+}
+
+#[test]
+fn common_year_test() {
+    assert!(!is_leap_year(2023));
+}
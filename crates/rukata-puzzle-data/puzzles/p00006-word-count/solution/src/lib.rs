@@ -0,0 +1,10 @@
+/// Counts the words in `text` that are separated by whitespace.
+///
+/// ```
+/// use p00006_word_count::count_words;
+///
+/// assert_eq!(count_words("one two  three"), 3);
+/// ```
+pub fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
@@ -0,0 +1,9 @@
+use std::io::Read;
+
+use p00006_word_count::count_words;
+
+fn main() {
+    let mut text = String::new();
+    std::io::stdin().read_to_string(&mut text).unwrap();
+    println!("{}", count_words(&text));
+}
@@ -0,0 +1,11 @@
+use p00006_word_count::count_words;
+
+#[test]
+fn empty_test() {
+    assert_eq!(count_words(""), 0);
+}
+
+#[test]
+fn sentence_test() {
+    assert_eq!(count_words("the quick brown fox"), 4);
+}
@@ -2,6 +2,8 @@
 use itertools::Itertools;
 use std::fmt;
 
+pub mod search;
+
 pub enum PuzzleFileEnum {
     File(&'static [u8]),
     String(&'static str),
@@ -38,7 +40,7 @@ impl PuzzleFileData {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PuzzleDifficulty {
     Basic,
     Intermediate,
@@ -46,6 +48,148 @@ pub enum PuzzleDifficulty {
     None,
 }
 
+/// Level a puzzle sets for a lint when `cargo clippy` is run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    /// The rustc flag setting this level, such as `-D`.
+    pub fn get_flag(&self) -> &'static str {
+        match self {
+            LintLevel::Allow => "-A",
+            LintLevel::Warn => "-W",
+            LintLevel::Deny => "-D",
+            LintLevel::Forbid => "-F",
+        }
+    }
+}
+
+/// What a puzzle asks of the attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleKind {
+    /// The attempt has to pass its check steps.
+    Standard,
+    /// Each compile-fail snippet has to fail to compile with its expected error.
+    CompileFail,
+    /// The implementation is given and the tests of the attempt have to catch each mutant.
+    WriteTests,
+}
+
+/// A file of a compile-fail puzzle that must not compile, and the error it has to fail with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompileFailSnippet {
+    pub(crate) path: &'static str,
+    pub(crate) code: &'static str,
+    pub(crate) message: Option<&'static str>,
+}
+
+impl CompileFailSnippet {
+    pub fn get_path(&self) -> &str {
+        self.path
+    }
+
+    /// The expected compiler error code, such as `E0382`.
+    pub fn get_code(&self) -> &str {
+        self.code
+    }
+
+    /// Regular expression the message of the error has to match.
+    pub fn get_message_pattern(&self) -> Option<&str> {
+        self.message
+    }
+}
+
+/// A buggy version of the implementation given by a write-the-tests puzzle.
+pub struct PuzzleMutant {
+    pub(crate) name: &'static str,
+    pub(crate) description: &'static str,
+    pub(crate) files: &'static [&'static PuzzleFileData],
+}
+
+impl PuzzleMutant {
+    pub fn get_name(&self) -> &str {
+        self.name
+    }
+
+    /// What the mutant gets wrong, shown when the tests do not catch it.
+    pub fn get_description(&self) -> &str {
+        self.description
+    }
+
+    /// Files replacing the given implementation.
+    pub fn get_files(&self) -> &[&PuzzleFileData] {
+        self.files
+    }
+}
+
+/// A later stage of a multi-stage puzzle, unlocked once every stage before it passes.
+pub struct PuzzleStage {
+    pub(crate) name: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) description: &'static str,
+    pub(crate) starter: &'static [&'static PuzzleFileData],
+    pub(crate) solution: &'static [&'static PuzzleFileData],
+    pub(crate) read_only_file_paths: &'static [&'static str],
+}
+
+impl PuzzleStage {
+    pub fn get_name(&self) -> &str {
+        self.name
+    }
+
+    pub fn get_title(&self) -> &str {
+        self.title
+    }
+
+    /// What the stage asks of the attempt, shown when it is unlocked.
+    pub fn get_description(&self) -> &str {
+        self.description
+    }
+
+    /// Files added to the attempt when the stage is unlocked.
+    pub fn get_starter_files(&self) -> &[&PuzzleFileData] {
+        self.starter
+    }
+
+    pub fn get_read_only_file_paths(&self) -> &[&str] {
+        self.read_only_file_paths
+    }
+
+    pub fn get_read_only_files(&self) -> Vec<&PuzzleFileData> {
+        self.starter
+            .iter()
+            .filter(|file_data| self.read_only_file_paths.contains(&file_data.relative_path))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A step `rukata check` runs against the attempt, in the order set by the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStep {
+    /// Runs `cargo test`, only the tests matching `filter` when it is set.
+    Test { filter: Option<&'static str> },
+    /// Runs a binary of the attempt with `stdin` and compares its output when expected output is set.
+    Run {
+        bin: Option<&'static str>,
+        arguments: &'static [&'static str],
+        stdin: &'static str,
+        stdout: Option<&'static str>,
+        stderr: Option<&'static str>,
+    },
+    /// Runs only the doc tests.
+    DocTest,
+    /// Builds the attempt with `features` enabled.
+    Build { features: &'static [&'static str] },
+    /// Runs a cargo subcommand, such as `bench --no-run`.
+    Cargo { arguments: &'static [&'static str] },
+}
+
 impl fmt::Display for PuzzleDifficulty {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
@@ -63,6 +207,18 @@ pub struct PuzzleData {
     pub(crate) difficulty: &'static PuzzleDifficulty,
     pub(crate) categories: &'static [&'static str],
     pub(crate) libraries: &'static [&'static str],
+    pub(crate) hints: &'static [&'static str],
+    pub(crate) error_explanations: &'static [(&'static str, &'static str)],
+    pub(crate) timeout: Option<u64>,
+    pub(crate) clippy_lints: &'static [(&'static str, LintLevel)],
+    pub(crate) check_formatting: bool,
+    pub(crate) hidden_tests: &'static [&'static PuzzleFileData],
+    pub(crate) check_steps: &'static [CheckStep],
+    pub(crate) kind: &'static PuzzleKind,
+    pub(crate) compile_fail: &'static [CompileFailSnippet],
+    pub(crate) mutants: &'static [PuzzleMutant],
+    pub(crate) requires: &'static [u16],
+    pub(crate) stages: &'static [PuzzleStage],
 }
 
 impl PuzzleData {
@@ -78,6 +234,20 @@ impl PuzzleData {
         self.readme.get_string_data()
     }
 
+    /// The README without the header and command added by the build.
+    pub fn get_readme_body(&self) -> &str {
+        let readme = self.get_readme_str();
+        let body = match readme.split_once('\n') {
+            Some((_, body)) => body,
+            None => readme,
+        };
+
+        match body.rfind("\n\n### Command\n") {
+            Some(index) => &body[..index],
+            None => body,
+        }
+    }
+
     pub fn get_readme_files(&self) -> &[&PuzzleFileData] {
         self.readme_files
     }
@@ -98,6 +268,72 @@ impl PuzzleData {
         self.libraries
     }
 
+    pub fn get_hints(&self) -> &[&str] {
+        self.hints
+    }
+
+    /// Puzzle specific explanation for a compiler error code such as `E0382`.
+    pub fn get_error_explanation(&self, code: &str) -> Option<&str> {
+        self.error_explanations
+            .iter()
+            .find(|(error_code, _)| *error_code == code)
+            .map(|(_, explanation)| *explanation)
+    }
+
+    /// Seconds `cargo test` may run for before the attempt is stopped.
+    pub fn get_timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    /// Lints `cargo clippy` must pass with, no clippy gate is run when this is empty.
+    pub fn get_clippy_lints(&self) -> &[(&str, LintLevel)] {
+        self.clippy_lints
+    }
+
+    /// Whether `cargo fmt --check` must pass.
+    pub fn get_check_formatting(&self) -> bool {
+        self.check_formatting
+    }
+
+    /// Tests only added to a copy of the attempt by `rukata check`, never generated.
+    pub fn get_hidden_tests(&self) -> &[&PuzzleFileData] {
+        self.hidden_tests
+    }
+
+    /// Steps `rukata check` runs in order, empty when the puzzle only needs `cargo test`.
+    pub fn get_check_steps(&self) -> &[CheckStep] {
+        self.check_steps
+    }
+
+    pub fn get_kind(&self) -> &PuzzleKind {
+        self.kind
+    }
+
+    /// Snippets that must not compile, only set for compile-fail puzzles.
+    pub fn get_compile_fail_snippets(&self) -> &[CompileFailSnippet] {
+        self.compile_fail
+    }
+
+    /// Mutants the tests of the attempt have to catch, only set for write-the-tests puzzles.
+    pub fn get_mutants(&self) -> &[PuzzleMutant] {
+        self.mutants
+    }
+
+    /// IDs of the puzzles that should be completed before this one.
+    pub fn get_requires(&self) -> &[u16] {
+        self.requires
+    }
+
+    /// Stages unlocked one at a time after the starter files pass, empty for a single stage puzzle.
+    pub fn get_stages(&self) -> &[PuzzleStage] {
+        self.stages
+    }
+
+    /// The number of stages, counting the starter files as the first.
+    pub fn get_stage_count(&self) -> usize {
+        self.stages.len() + 1
+    }
+
     pub fn get_base_files(&self) -> Vec<&PuzzleFileData> {
         self.starter
             .iter()
@@ -122,6 +358,22 @@ impl PuzzleData {
         files
     }
 
+    /// The read-only files of the attempt once `stage` is unlocked, counting from 0.
+    pub fn get_stage_read_only_files(&self, stage: usize) -> Vec<&PuzzleFileData> {
+        let mut files = self.get_read_only_files();
+        for puzzle_stage in self.stages.iter().take(stage) {
+            files.retain(|file_data| {
+                !puzzle_stage
+                    .starter
+                    .iter()
+                    .any(|other| other.relative_path == file_data.relative_path)
+            });
+            files.extend(puzzle_stage.get_read_only_files());
+        }
+
+        files
+    }
+
     pub fn get_final_files(&self) -> Vec<&PuzzleFileData> {
         let mut files: Vec<&PuzzleFileData> = [self.readme]
             .iter()
@@ -129,13 +381,59 @@ impl PuzzleData {
             .cloned()
             .collect();
 
-        files.extend(self.get_read_only_files());
+        files.extend(self.get_stage_read_only_files(self.stages.len()));
         files.extend(self.solution);
+        for puzzle_stage in self.stages {
+            overlay_files(&mut files, puzzle_stage.solution.to_vec());
+        }
 
         files
     }
 }
 
+/// Adds `overlay` to `files`, replacing the files with the same path.
+fn overlay_files<'data>(
+    files: &mut Vec<&'data PuzzleFileData>,
+    overlay: Vec<&'data PuzzleFileData>,
+) {
+    for file_data in overlay {
+        match files
+            .iter()
+            .position(|other| other.relative_path == file_data.relative_path)
+        {
+            Some(index) => files[index] = file_data,
+            None => files.push(file_data),
+        }
+    }
+}
+
+/// A named, ordered list of puzzles to work through.
+pub struct PuzzleTrack {
+    pub(crate) id: &'static str,
+    pub(crate) title: &'static str,
+    pub(crate) description: &'static str,
+    pub(crate) puzzles: &'static [u16],
+}
+
+impl PuzzleTrack {
+    pub fn get_id(&self) -> &str {
+        self.id
+    }
+
+    pub fn get_title(&self) -> &str {
+        self.title
+    }
+
+    pub fn get_description(&self) -> &str {
+        self.description
+    }
+
+    /// Puzzle IDs in the order to work through them.
+    pub fn get_puzzles(&self) -> &[u16] {
+        self.puzzles
+    }
+}
+
 include!(concat!(env!("OUT_DIR"), "/codegen.rs"));
 
 pub fn get_file_data(id: u16) -> Option<&'static PuzzleData> {
@@ -146,7 +444,48 @@ pub fn get_file_data(id: u16) -> Option<&'static PuzzleData> {
     }
 }
 
+/// Every track, in the order of the tracks file.
+pub fn get_tracks() -> &'static [PuzzleTrack] {
+    TRACKS
+}
+
+pub fn get_track(id: &str) -> Option<&'static PuzzleTrack> {
+    TRACKS.iter().find(|track| track.id == id)
+}
+
 #[cfg(feature = "list")]
 pub fn get_id_list() -> Vec<&'static u16> {
     PUZZLES.keys().sorted().collect_vec()
 }
+
+/// IDs of the puzzles that require the given puzzle, in ID order.
+#[cfg(feature = "list")]
+pub fn get_required_by(id: u16) -> Vec<&'static u16> {
+    get_id_list()
+        .into_iter()
+        .filter(|other_id| {
+            PUZZLES
+                .get(*other_id)
+                .is_some_and(|puzzle_data| puzzle_data.requires.contains(&id))
+        })
+        .collect_vec()
+}
+
+/// Puzzle IDs with every puzzle after the puzzles it requires, otherwise in ID order.
+#[cfg(feature = "list")]
+pub fn get_ordered_id_list() -> Vec<&'static u16> {
+    let mut ordered: Vec<&'static u16> = Vec::new();
+    let mut remaining = get_id_list();
+
+    // The build rejects cycles, so a puzzle is always ready until every puzzle is ordered.
+    while let Some(index) = remaining.iter().position(|id| {
+        PUZZLES[*id]
+            .requires
+            .iter()
+            .all(|required_id| ordered.contains(&required_id))
+    }) {
+        ordered.push(remaining.remove(index));
+    }
+
+    ordered
+}
@@ -0,0 +1,318 @@
+use crate::{PuzzleData, PUZZLES};
+use std::fmt;
+use std::ops::Range;
+
+// Scores given to each field a term is found in.
+const TITLE_SCORE: u32 = 10;
+const CATEGORY_SCORE: u32 = 8;
+const LIBRARY_SCORE: u32 = 8;
+const README_SCORE: u32 = 1;
+const README_MAX_SCORE: u32 = 5;
+const EXACT_SCORE: u32 = 4;
+const PHRASE_SCORE: u32 = 15;
+
+// Number of bytes shown either side of a README match.
+const SNIPPET_CONTEXT: usize = 60;
+
+// Shorter terms match too many words to be useful.
+const MIN_TERM_LENGTH: usize = 3;
+
+// Common words that are left out of a query.
+const STOP_WORDS: &[&str] = &[
+    "about", "and", "are", "but", "can", "for", "from", "has", "have", "how", "into", "not", "one",
+    "puzzle", "puzzles", "that", "the", "their", "then", "there", "this", "use", "using", "was",
+    "what", "when", "where", "which", "who", "why", "with",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Title,
+    Category,
+    Library,
+    Readme,
+}
+
+impl fmt::Display for SearchField {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SearchField::Title => write!(f, "Title"),
+            SearchField::Category => write!(f, "Category"),
+            SearchField::Library => write!(f, "Library"),
+            SearchField::Readme => write!(f, "README"),
+        }
+    }
+}
+
+pub struct SearchMatch {
+    field: SearchField,
+    snippet: String,
+    highlights: Vec<Range<usize>>,
+}
+
+impl SearchMatch {
+    pub fn get_field(&self) -> SearchField {
+        self.field
+    }
+
+    pub fn get_snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    /// Byte ranges of the matched terms within the snippet.
+    pub fn get_highlights(&self) -> &[Range<usize>] {
+        &self.highlights
+    }
+}
+
+pub struct SearchResult {
+    puzzle_data: &'static PuzzleData,
+    score: u32,
+    matches: Vec<SearchMatch>,
+}
+
+impl SearchResult {
+    pub fn get_puzzle_data(&self) -> &'static PuzzleData {
+        self.puzzle_data
+    }
+
+    pub fn get_score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn get_matches(&self) -> &[SearchMatch] {
+        &self.matches
+    }
+}
+
+fn get_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .map(|term| term.to_ascii_lowercase())
+        .filter(|term| {
+            term.chars().count() >= MIN_TERM_LENGTH && !STOP_WORDS.contains(&term.as_str())
+        })
+        .collect();
+    terms.sort();
+    terms.dedup();
+    terms
+}
+
+/// Finds where `term` matches the start of a word in the lowercase `text`.
+///
+/// A term matches a whole word or a prefix of one, so `iter` matches `iterator` but not `filter`.
+fn find_term<'a>(lowercase: &'a str, term: &'a str) -> impl Iterator<Item = Range<usize>> + 'a {
+    lowercase
+        .match_indices(term)
+        .filter(|(start, _)| {
+            !lowercase[..*start]
+                .chars()
+                .next_back()
+                .is_some_and(char::is_alphanumeric)
+        })
+        .map(|(start, matched)| start..start + matched.len())
+}
+
+fn find_ranges(text: &str, terms: &[String]) -> Vec<Range<usize>> {
+    let lowercase = text.to_ascii_lowercase();
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for term in terms {
+        ranges.extend(find_term(&lowercase, term));
+    }
+
+    // Merge overlapping ranges so that they can be highlighted in order.
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+fn get_snippet(text: &str, range: &Range<usize>) -> String {
+    let mut start = range.start.saturating_sub(SNIPPET_CONTEXT);
+    while !text.is_char_boundary(start) {
+        start -= 1;
+    }
+
+    let mut end = (range.end + SNIPPET_CONTEXT).min(text.len());
+    while !text.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let mut snippet = text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if start > 0 {
+        snippet = format!("...{}", snippet);
+    }
+    if end < text.len() {
+        snippet.push_str("...");
+    }
+
+    snippet
+}
+
+fn search_field(
+    field: SearchField,
+    text: &str,
+    terms: &[String],
+    score: u32,
+    result: &mut SearchResult,
+) -> Vec<String> {
+    let lowercase = text.to_ascii_lowercase();
+    let matched_terms: Vec<String> = terms
+        .iter()
+        .filter(|term| find_term(&lowercase, term).next().is_some())
+        .cloned()
+        .collect();
+    if matched_terms.is_empty() {
+        return matched_terms;
+    }
+
+    for term in &matched_terms {
+        result.score += score;
+        if lowercase == *term {
+            result.score += EXACT_SCORE;
+        }
+    }
+
+    result.matches.push(SearchMatch {
+        field,
+        snippet: text.to_string(),
+        highlights: find_ranges(text, terms),
+    });
+
+    matched_terms
+}
+
+fn search_readme(text: &str, terms: &[String], result: &mut SearchResult) -> Vec<String> {
+    let lowercase = text.to_ascii_lowercase();
+    let matched_terms: Vec<String> = terms
+        .iter()
+        .filter(|term| find_term(&lowercase, term).next().is_some())
+        .cloned()
+        .collect();
+
+    let ranges = find_ranges(text, terms);
+    let first_range = match ranges.first() {
+        Some(range) => range,
+        None => return matched_terms,
+    };
+
+    for term in &matched_terms {
+        let count = find_term(&lowercase, term).count() as u32;
+        result.score += (count * README_SCORE).min(README_MAX_SCORE);
+    }
+
+    let snippet = get_snippet(text, first_range);
+    let highlights = find_ranges(&snippet, terms);
+    result.matches.push(SearchMatch {
+        field: SearchField::Readme,
+        snippet,
+        highlights,
+    });
+
+    matched_terms
+}
+
+fn search_puzzle(
+    puzzle_data: &'static PuzzleData,
+    query: &str,
+    terms: &[String],
+) -> Option<SearchResult> {
+    let mut result = SearchResult {
+        puzzle_data,
+        score: 0,
+        matches: Vec::new(),
+    };
+
+    let mut matched_terms = search_field(
+        SearchField::Title,
+        puzzle_data.get_title(),
+        terms,
+        TITLE_SCORE,
+        &mut result,
+    );
+    for category in puzzle_data.get_categories() {
+        matched_terms.extend(search_field(
+            SearchField::Category,
+            category,
+            terms,
+            CATEGORY_SCORE,
+            &mut result,
+        ));
+    }
+    for library in puzzle_data.get_libraries() {
+        matched_terms.extend(search_field(
+            SearchField::Library,
+            library,
+            terms,
+            LIBRARY_SCORE,
+            &mut result,
+        ));
+    }
+    matched_terms.extend(search_readme(
+        puzzle_data.get_readme_body(),
+        terms,
+        &mut result,
+    ));
+
+    if result.matches.is_empty() {
+        return None;
+    }
+
+    // Favour puzzles that match more of the query.
+    matched_terms.sort();
+    matched_terms.dedup();
+    result.score *= matched_terms.len() as u32;
+
+    // Favour puzzles that contain the whole query as written.
+    let phrase = query.trim().to_ascii_lowercase();
+    if terms.len() > 1
+        && (puzzle_data
+            .get_title()
+            .to_ascii_lowercase()
+            .contains(&phrase)
+            || puzzle_data
+                .get_readme_body()
+                .to_ascii_lowercase()
+                .contains(&phrase))
+    {
+        result.score += PHRASE_SCORE;
+    }
+
+    Some(result)
+}
+
+/// Search the titles, READMEs, categories and libraries of every puzzle.
+///
+/// Each word of the query matches whole words or the start of words. Words shorter than three
+/// letters and common words such as `the` or `about` are ignored.
+///
+/// Results are ordered from the best match to the worst, ties are ordered by puzzle ID.
+pub fn search(query: &str) -> Vec<SearchResult> {
+    let terms = get_terms(query);
+    if terms.is_empty() {
+        return Vec::new();
+    }
+
+    let mut results: Vec<SearchResult> = PUZZLES
+        .values()
+        .filter_map(|puzzle_data| search_puzzle(puzzle_data, query, &terms))
+        .collect();
+
+    results.sort_by(|left, right| {
+        right
+            .score
+            .cmp(&left.score)
+            .then(left.puzzle_data.id.cmp(right.puzzle_data.id))
+    });
+
+    results
+}
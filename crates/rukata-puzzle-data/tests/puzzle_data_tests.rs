@@ -0,0 +1,213 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+use rukata_puzzle_data::{get_file_data, get_track, get_tracks, CheckStep, LintLevel, PuzzleKind};
+
+#[test]
+fn test_error_explanation() {
+    let puzzle_data = get_file_data(0).unwrap();
+    assert!(puzzle_data.get_error_explanation("E0308").is_some());
+    assert_eq!(puzzle_data.get_error_explanation("E0000"), None);
+}
+
+#[test]
+fn test_gates() {
+    let puzzle_data = get_file_data(0).unwrap();
+    assert!(puzzle_data.get_clippy_lints().is_empty());
+    assert!(!puzzle_data.get_check_formatting());
+
+    let puzzle_data = get_file_data(4).unwrap();
+    assert_eq!(
+        puzzle_data.get_clippy_lints(),
+        &[
+            ("clippy::needless_range_loop", LintLevel::Deny),
+            ("clippy::needless_return", LintLevel::Deny)
+        ]
+    );
+    assert_eq!(puzzle_data.get_clippy_lints()[0].1.get_flag(), "-D");
+    assert!(puzzle_data.get_check_formatting());
+    assert!(puzzle_data
+        .get_error_explanation("clippy::needless_return")
+        .is_some());
+}
+
+#[test]
+fn test_hidden_tests() {
+    assert!(get_file_data(0).unwrap().get_hidden_tests().is_empty());
+
+    let puzzle_data = get_file_data(5).unwrap();
+    let hidden_tests = puzzle_data.get_hidden_tests();
+    assert_eq!(hidden_tests.len(), 1);
+    assert_eq!(hidden_tests[0].get_relative_path(), "tests/hidden.rs");
+
+    // Hidden tests are never written out with the puzzle.
+    assert!(!puzzle_data
+        .get_base_files()
+        .iter()
+        .chain(puzzle_data.get_final_files().iter())
+        .any(|file_data| file_data.get_relative_path() == "tests/hidden.rs"));
+}
+
+#[test]
+fn test_check_steps() {
+    assert!(get_file_data(0).unwrap().get_check_steps().is_empty());
+
+    let puzzle_data = get_file_data(6).unwrap();
+    assert_eq!(
+        puzzle_data.get_check_steps(),
+        &[
+            CheckStep::Test { filter: None },
+            CheckStep::DocTest,
+            CheckStep::Run {
+                bin: None,
+                arguments: &[],
+                stdin: "Rust puzzles\nare  fun\n",
+                stdout: Some("4"),
+                stderr: None
+            }
+        ]
+    );
+
+    // The binary reading standard input can not be changed.
+    assert!(puzzle_data
+        .get_read_only_file_paths()
+        .contains(&"src/main.rs"));
+}
+
+#[test]
+fn test_compile_fail_snippets() {
+    let puzzle_data = get_file_data(0).unwrap();
+    assert_eq!(puzzle_data.get_kind(), &PuzzleKind::Standard);
+    assert!(puzzle_data.get_compile_fail_snippets().is_empty());
+
+    let puzzle_data = get_file_data(1).unwrap();
+    assert_eq!(puzzle_data.get_kind(), &PuzzleKind::CompileFail);
+
+    let snippets = puzzle_data.get_compile_fail_snippets();
+    assert_eq!(snippets.len(), 2);
+    assert_eq!(snippets[0].get_path(), "snippets/spend_twice.rs");
+    assert_eq!(snippets[0].get_code(), "E0382");
+    assert_eq!(
+        snippets[0].get_message_pattern(),
+        Some("^use of moved value")
+    );
+
+    // Snippets are generated with the puzzle and can not be changed.
+    assert!(puzzle_data
+        .get_read_only_file_paths()
+        .contains(&"snippets/spend_twice.rs"));
+}
+
+#[test]
+fn test_mutants() {
+    let puzzle_data = get_file_data(2).unwrap();
+    assert_eq!(puzzle_data.get_kind(), &PuzzleKind::WriteTests);
+
+    let mutants = puzzle_data.get_mutants();
+    assert_eq!(mutants.len(), 3);
+    assert_eq!(mutants[0].get_name(), "no_lower_bound");
+    assert_eq!(mutants[0].get_files().len(), 1);
+    assert_eq!(mutants[0].get_files()[0].get_relative_path(), "src/lib.rs");
+
+    // Each mutant differs from the given implementation.
+    let implementation = puzzle_data
+        .get_read_only_files()
+        .into_iter()
+        .find(|file_data| file_data.get_relative_path() == "src/lib.rs")
+        .unwrap();
+    assert!(mutants
+        .iter()
+        .all(|mutant| !implementation.check_data(mutant.get_files()[0].get_raw_data())));
+
+    assert!(get_file_data(0).unwrap().get_mutants().is_empty());
+}
+
+#[test]
+fn test_requires() {
+    assert_eq!(get_file_data(0).unwrap().get_requires(), &[] as &[u16]);
+    assert_eq!(get_file_data(2).unwrap().get_requires(), &[0]);
+}
+
+#[test]
+fn test_tracks() {
+    let track = get_track("ownership-fundamentals").unwrap();
+    assert_eq!(track.get_title(), "Ownership fundamentals");
+    assert_eq!(track.get_puzzles(), &[0, 1]);
+    assert!(get_track("missing").is_none());
+
+    // The tracks keep the order of the tracks file and only list known puzzles.
+    assert_eq!(get_tracks()[0].get_id(), "getting-started");
+    for track in get_tracks() {
+        assert!(track
+            .get_puzzles()
+            .iter()
+            .all(|puzzle_id| get_file_data(*puzzle_id).is_some()));
+    }
+}
+
+#[test]
+fn test_stages() {
+    let puzzle_data = get_file_data(0).unwrap();
+    assert!(puzzle_data.get_stages().is_empty());
+    assert_eq!(puzzle_data.get_stage_count(), 1);
+
+    let puzzle_data = get_file_data(3).unwrap();
+    assert_eq!(puzzle_data.get_stage_count(), 3);
+    let stages = puzzle_data.get_stages();
+    assert_eq!(stages[0].get_name(), "generic");
+    assert_eq!(stages[1].get_title(), "Add an iterator");
+    assert_eq!(
+        stages[0].get_read_only_file_paths(),
+        &["tests/generic.rs"] as &[&str]
+    );
+
+    // The tests of a stage are only read-only once it is unlocked.
+    let get_paths = |stage: usize| {
+        puzzle_data
+            .get_stage_read_only_files(stage)
+            .iter()
+            .map(|file_data| file_data.get_relative_path().to_string())
+            .collect::<Vec<String>>()
+    };
+    assert!(!get_paths(0).contains(&"tests/generic.rs".to_string()));
+    assert!(get_paths(1).contains(&"tests/generic.rs".to_string()));
+    assert!(!get_paths(1).contains(&"tests/iterator.rs".to_string()));
+    assert!(get_paths(2).contains(&"tests/iterator.rs".to_string()));
+
+    // The starter tests stay read-only, the code the user writes never is.
+    for stage in 0..puzzle_data.get_stage_count() {
+        assert!(get_paths(stage).contains(&"tests/test.rs".to_string()));
+        assert!(!get_paths(stage).contains(&"src/lib.rs".to_string()));
+        assert!(!get_paths(stage).contains(&"src/squares.rs".to_string()));
+    }
+
+    // The solution is the one of the last stage, along with the tests of every stage.
+    let final_files = puzzle_data.get_final_files();
+    let implementations: Vec<_> = final_files
+        .iter()
+        .filter(|file_data| file_data.get_relative_path() == "src/lib.rs")
+        .collect();
+    assert_eq!(implementations.len(), 1);
+    assert!(String::from_utf8_lossy(implementations[0].get_raw_data())
+        .contains("pub use squares::Squares;"));
+    assert!(final_files
+        .iter()
+        .any(|file_data| file_data.get_relative_path() == "tests/generic.rs"));
+}
+
+#[cfg(feature = "list")]
+#[test]
+fn test_ordered_id_list() {
+    use rukata_puzzle_data::{get_ordered_id_list, get_required_by};
+
+    let ordered = get_ordered_id_list();
+    assert_eq!(ordered.len(), rukata_puzzle_data::get_id_list().len());
+    for (index, id) in ordered.iter().enumerate() {
+        for required_id in get_file_data(**id).unwrap().get_requires() {
+            assert!(ordered[..index].contains(&required_id));
+        }
+    }
+
+    assert_eq!(get_required_by(0), vec![&1, &2, &3, &4, &5, &6]);
+    assert_eq!(get_required_by(2), Vec::<&u16>::new());
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+use rukata_puzzle_data::get_file_data;
+use rukata_puzzle_data::search::{search, SearchField};
+
+#[test]
+fn test_readme_body() {
+    let puzzle_data = get_file_data(0).unwrap();
+    assert_eq!(
+        puzzle_data.get_readme_body(),
+        "A simple puzzle to help understand the workflow of the puzzles."
+    );
+}
+
+#[test]
+fn test_search_title() {
+    let results = search("ADDITION");
+    assert_eq!(results.len(), 1);
+
+    let result = &results[0];
+    assert_eq!(*result.get_puzzle_data().get_id(), 0);
+
+    let title_match = &result.get_matches()[0];
+    assert_eq!(title_match.get_field(), SearchField::Title);
+    assert_eq!(title_match.get_snippet(), "Basic Addition");
+    assert_eq!(title_match.get_highlights().len(), 1);
+    assert_eq!(title_match.get_highlights()[0], 6..14);
+}
+
+#[test]
+fn test_search_readme() {
+    let results = search("workflow");
+    assert_eq!(results.len(), 1);
+
+    let readme_match = &results[0].get_matches()[0];
+    assert_eq!(readme_match.get_field(), SearchField::Readme);
+    assert_eq!(
+        &readme_match.get_snippet()[readme_match.get_highlights()[0].clone()],
+        "workflow"
+    );
+}
+
+#[test]
+fn test_search_more_terms_score_higher() {
+    let single = search("tutorial");
+    let multiple = search("tutorial addition");
+    assert!(multiple[0].get_score() > single[0].get_score());
+}
+
+#[test]
+fn test_search_no_match() {
+    assert!(search("xyzzy").is_empty());
+    assert!(search("  ").is_empty());
+}
+
+#[test]
+fn test_search_natural_query() {
+    let results = search("the puzzle about iterator adaptors");
+    let ids: Vec<u16> = results
+        .iter()
+        .map(|result| *result.get_puzzle_data().get_id())
+        .collect();
+    // The `Iterators` category of puzzle 3 scores above the README of puzzle 4, while the other
+    // puzzles only contain the words that are ignored.
+    assert_eq!(ids, vec![3, 4]);
+    assert!(results[0].get_score() > results[1].get_score());
+
+    // Short and common words are not searched for on their own.
+    assert!(search("the puzzle about it").is_empty());
+}
+
+#[test]
+fn test_search_word_prefix() {
+    let results = search("add");
+    assert_eq!(*results[0].get_puzzle_data().get_id(), 0);
+    assert_eq!(results[0].get_matches()[0].get_highlights()[0], 6..9);
+
+    // Terms only match from the start of a word.
+    assert!(search("dition").is_empty());
+}
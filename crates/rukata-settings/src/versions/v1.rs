@@ -1,10 +1,95 @@
 use camino::Utf8PathBuf;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 
+/// How cargo is run when checking a puzzle.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CargoSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) binary: Option<Utf8PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) toolchain: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub(crate) offline: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) target_directory: Option<Utf8PathBuf>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) arguments: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) environment: BTreeMap<String, String>,
+}
+
+impl CargoSettings {
+    pub fn is_default(&self) -> bool {
+        *self == CargoSettings::default()
+    }
+
+    /// Cargo binary to run instead of the one on the `PATH`.
+    pub fn get_binary(&self) -> Option<&Utf8PathBuf> {
+        self.binary.as_ref()
+    }
+
+    pub fn set_binary(&mut self, path: Option<Utf8PathBuf>) {
+        self.binary = path
+    }
+
+    /// Rustup toolchain passed to cargo as `+toolchain`, or as `RUSTUP_TOOLCHAIN` with another binary.
+    pub fn get_toolchain(&self) -> Option<&str> {
+        self.toolchain.as_deref()
+    }
+
+    pub fn set_toolchain(&mut self, toolchain: Option<String>) {
+        self.toolchain = toolchain.map(|toolchain| toolchain.trim_start_matches('+').to_string())
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline
+    }
+
+    /// Target directory shared by every puzzle instead of one per puzzle.
+    pub fn get_target_directory(&self) -> Option<&Utf8PathBuf> {
+        self.target_directory.as_ref()
+    }
+
+    pub fn set_target_directory(&mut self, path: Option<Utf8PathBuf>) {
+        self.target_directory = path
+    }
+
+    /// Extra arguments added to the cargo commands that build the puzzle, `build`, `test` and `run`.
+    pub fn get_arguments(&self) -> &[String] {
+        &self.arguments
+    }
+
+    pub fn set_arguments(&mut self, arguments: Vec<String>) {
+        self.arguments = arguments
+    }
+
+    /// Extra environment variables set for every cargo command.
+    pub fn get_environment(&self) -> &BTreeMap<String, String> {
+        &self.environment
+    }
+
+    pub fn set_environment(&mut self, environment: BTreeMap<String, String>) {
+        self.environment = environment
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Settings {
     pub(crate) directory: Utf8PathBuf,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) test_timeout: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) memory_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) cpu_limit: Option<u64>,
+    #[serde(default, skip_serializing_if = "CargoSettings::is_default")]
+    pub(crate) cargo: CargoSettings,
 }
 
 impl fmt::Display for Settings {
@@ -25,4 +110,39 @@ impl Settings {
     pub fn set_directory<P: Into<Utf8PathBuf>>(&mut self, path: P) {
         self.directory = path.into()
     }
+
+    /// Seconds `cargo test` may run for, unless the puzzle sets its own timeout.
+    pub fn get_test_timeout(&self) -> Option<u64> {
+        self.test_timeout
+    }
+
+    pub fn set_test_timeout(&mut self, seconds: Option<u64>) {
+        self.test_timeout = seconds
+    }
+
+    /// Memory limit in MiB for the processes run by `cargo test`.
+    pub fn get_memory_limit(&self) -> Option<u64> {
+        self.memory_limit
+    }
+
+    pub fn set_memory_limit(&mut self, megabytes: Option<u64>) {
+        self.memory_limit = megabytes
+    }
+
+    /// CPU time limit in seconds for each process run by `cargo test`.
+    pub fn get_cpu_limit(&self) -> Option<u64> {
+        self.cpu_limit
+    }
+
+    pub fn set_cpu_limit(&mut self, seconds: Option<u64>) {
+        self.cpu_limit = seconds
+    }
+
+    pub fn get_cargo(&self) -> &CargoSettings {
+        &self.cargo
+    }
+
+    pub fn get_mut_cargo(&mut self) -> &mut CargoSettings {
+        &mut self.cargo
+    }
 }
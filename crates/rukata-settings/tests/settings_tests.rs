@@ -99,3 +99,44 @@ fn test_valid_basic() {
         assert_eq!(settings.get_directory(), Utf8PathBuf::new())
     }
 }
+
+#[test]
+fn test_valid_limits() {
+    let path = FILES_DIR.join("valid_limits.json");
+    let settings_handler = SettingsHandler::new(path).unwrap();
+    let settings = settings_handler.get_settings();
+    assert_eq!(settings.get_test_timeout(), Some(30));
+    assert_eq!(settings.get_memory_limit(), Some(512));
+    assert_eq!(settings.get_cpu_limit(), None);
+}
+
+#[test]
+fn test_valid_cargo() {
+    let path = FILES_DIR.join("valid_cargo.json");
+    let settings_handler = SettingsHandler::new(path).unwrap();
+    let cargo = settings_handler.get_settings().get_cargo();
+    assert_eq!(cargo.get_binary(), None);
+    assert_eq!(cargo.get_toolchain(), Some("stable"));
+    assert!(cargo.is_offline());
+    assert_eq!(
+        cargo.get_target_directory(),
+        Some(&Utf8PathBuf::from("/tmp/rukata-target"))
+    );
+    assert_eq!(
+        cargo.get_arguments(),
+        &["--jobs".to_string(), "2".to_string()]
+    );
+    assert_eq!(
+        cargo.get_environment().get("RUSTFLAGS"),
+        Some(&"-Dwarnings".to_string())
+    );
+}
+
+#[test]
+fn test_set_toolchain() {
+    let mut settings_handler = SettingsHandler::new(FILES_DIR.join("default.json")).unwrap();
+    let cargo = settings_handler.get_mut_settings().get_mut_cargo();
+    cargo.set_toolchain(Some("+nightly".to_string()));
+    assert_eq!(cargo.get_toolchain(), Some("nightly"));
+    assert!(!cargo.is_default());
+}
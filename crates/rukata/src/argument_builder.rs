@@ -1,5 +1,8 @@
+use crate::reporter::OutputFormat;
 use camino::Utf8PathBuf;
-use clap::{Command, Parser, Subcommand};
+use clap::{Args, Command, Parser, Subcommand, ValueEnum};
+use rukata_progress::versions::v1::PuzzleState;
+use rukata_puzzle_data::PuzzleDifficulty;
 
 #[derive(Parser, Debug)]
 pub struct GeneralArguments {
@@ -12,6 +15,198 @@ pub struct SettingsArguments {
     /// Directory for Rukata to use
     #[arg(short, long)]
     pub directory: Option<Utf8PathBuf>,
+    /// Seconds `cargo test` may run for during a check, 0 for no timeout
+    #[arg(long, value_name = "SECONDS")]
+    pub test_timeout: Option<u64>,
+    /// Memory limit in MiB for `cargo test` on Linux, 0 for no limit
+    #[arg(long, value_name = "MIB")]
+    pub memory_limit: Option<u64>,
+    /// CPU time limit in seconds for `cargo test` on Linux, 0 for no limit
+    #[arg(long, value_name = "SECONDS")]
+    pub cpu_limit: Option<u64>,
+    /// Cargo binary to run instead of `cargo`
+    #[arg(long, value_name = "PATH")]
+    pub cargo_binary: Option<Utf8PathBuf>,
+    /// Rustup toolchain to run cargo with, such as `stable`
+    #[arg(long)]
+    pub toolchain: Option<String>,
+    /// Run cargo with `--offline`
+    #[arg(long, value_name = "BOOL")]
+    pub offline: Option<bool>,
+    /// Target directory shared by every puzzle
+    #[arg(long, value_name = "PATH")]
+    pub target_directory: Option<Utf8PathBuf>,
+    /// Extra argument for the cargo commands that build the puzzle, replacing the current ones
+    #[arg(long = "cargo-arg", value_name = "ARG", allow_hyphen_values = true)]
+    pub cargo_arguments: Vec<String>,
+    /// Extra environment variable for every cargo command, replacing the current ones
+    #[arg(long = "cargo-env", value_name = "KEY=VALUE", value_parser = parse_environment_variable)]
+    pub cargo_environment: Vec<(String, String)>,
+    /// Remove all of the cargo settings
+    #[arg(long)]
+    pub reset_cargo: bool,
+}
+
+fn parse_environment_variable(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("`{}` is not in the form KEY=VALUE", value)),
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum FormatArgument {
+    #[default]
+    Human,
+    Json,
+}
+
+impl From<FormatArgument> for OutputFormat {
+    fn from(value: FormatArgument) -> Self {
+        match value {
+            FormatArgument::Human => OutputFormat::Human,
+            FormatArgument::Json => OutputFormat::Json,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct GlobalArguments {
+    /// Format to print the output in
+    #[arg(long, value_enum, default_value_t, global = true)]
+    pub format: FormatArgument,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum DifficultyArgument {
+    Basic,
+    Intermediate,
+    Advanced,
+    None,
+}
+
+impl From<DifficultyArgument> for PuzzleDifficulty {
+    fn from(value: DifficultyArgument) -> Self {
+        match value {
+            DifficultyArgument::Basic => PuzzleDifficulty::Basic,
+            DifficultyArgument::Intermediate => PuzzleDifficulty::Intermediate,
+            DifficultyArgument::Advanced => PuzzleDifficulty::Advanced,
+            DifficultyArgument::None => PuzzleDifficulty::None,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum StateArgument {
+    NotStarted,
+    InProgress,
+    Completed,
+}
+
+impl From<StateArgument> for PuzzleState {
+    fn from(value: StateArgument) -> Self {
+        match value {
+            StateArgument::NotStarted => PuzzleState::NotStarted,
+            StateArgument::InProgress => PuzzleState::InProgress,
+            StateArgument::Completed => PuzzleState::Completed,
+        }
+    }
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default)]
+pub enum SortArgument {
+    /// Every puzzle after the puzzles it requires, otherwise by ID
+    #[default]
+    Planned,
+    Id,
+    Title,
+    Difficulty,
+}
+
+#[derive(Parser, Debug)]
+pub struct ListArguments {
+    /// Only list puzzles with this difficulty
+    #[arg(short, long)]
+    pub difficulty: Option<DifficultyArgument>,
+    /// Only list puzzles with this category
+    #[arg(short, long)]
+    pub category: Option<String>,
+    /// Only list puzzles using this library
+    #[arg(short, long)]
+    pub library: Option<String>,
+    /// Only list puzzles in this state
+    #[arg(short, long)]
+    pub state: Option<StateArgument>,
+    /// Order to list the puzzles in
+    #[arg(long, value_enum, default_value_t)]
+    pub sort: SortArgument,
+    /// Reverse the listing order
+    #[arg(short, long)]
+    pub reverse: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct SearchArguments {
+    /// Words to search for
+    #[arg(required = true)]
+    pub query: Vec<String>,
+    /// Maximum number of puzzles to show
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub limit: usize,
+}
+
+#[derive(Parser, Debug)]
+pub struct ResetArguments {
+    /// Puzzle ID to use
+    pub puzzle_id: u16,
+    /// List the backups of the puzzle
+    #[arg(short, long, conflicts_with = "restore")]
+    pub list: bool,
+    /// Restore the named backup instead of the starter files
+    #[arg(short, long)]
+    pub restore: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct RedoArguments {
+    /// Puzzle ID to use
+    pub puzzle_id: u16,
+    /// List the archived attempts of the puzzle
+    #[arg(short, long, conflicts_with = "open")]
+    pub list: bool,
+    /// Show the archived attempt with this number instead of starting a new one
+    #[arg(short, long)]
+    pub open: Option<usize>,
+}
+
+#[derive(Parser, Debug)]
+pub struct ReviewArguments {
+    /// Only show the review schedule without generating the due puzzles
+    #[arg(short, long)]
+    pub list: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct NextArguments {
+    /// Generate the recommended puzzle without asking
+    #[arg(short, long)]
+    pub generate: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct TrackArguments {
+    /// Track ID to use, such as `getting-started`
+    pub track_id: String,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TrackCommands {
+    /// Lists the tracks and the progress through each one
+    List,
+    /// Shows the puzzles of a track and the progress through them
+    Show(TrackArguments),
+    /// Generates the first puzzle of a track that has not been completed
+    Start(TrackArguments),
 }
 
 #[derive(Subcommand, Debug)]
@@ -24,6 +219,29 @@ pub enum SubCommands {
     Solution(GeneralArguments),
     /// Modify settings used by Rukata
     Settings(SettingsArguments),
+    /// Shows the recorded progress for each puzzle
+    Progress,
+    /// Lists the available puzzles
+    List(ListArguments),
+    /// Searches the puzzle titles, READMEs, categories and libraries
+    Search(SearchArguments),
+    /// Backs up and resets the specified Puzzle ID to its starter files
+    Reset(ResetArguments),
+    /// Archives the current attempt at the specified Puzzle ID and generates it again
+    Redo(RedoArguments),
+    /// Shows the review schedule and generates the puzzles that are due again
+    Review(ReviewArguments),
+    /// Recommends the next puzzle to work on and offers to generate it
+    Next(NextArguments),
+    /// Lists, shows and starts the curated tracks of puzzles
+    #[command(subcommand)]
+    Track(TrackCommands),
+    /// Shows the differences between the specified Puzzle ID and its solution
+    Diff(GeneralArguments),
+    /// Reveals the next hint for the specified Puzzle ID
+    Hint(GeneralArguments),
+    /// Checks the specified Puzzle ID every time a file changes
+    Watch(GeneralArguments),
 }
 
 pub fn generate_command() -> Command {
@@ -31,5 +249,6 @@ pub fn generate_command() -> Command {
         .version(env!("CARGO_PKG_VERSION"))
         .arg_required_else_help(true);
 
+    let cli = GlobalArguments::augment_args(cli);
     SubCommands::augment_subcommands(cli)
 }
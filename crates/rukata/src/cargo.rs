@@ -0,0 +1,616 @@
+use crate::error::RukataError;
+use camino::{Utf8Path, Utf8PathBuf};
+use console::Term;
+use rukata_settings::versions::v1::CargoSettings;
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[cfg(windows)]
+pub(crate) static CARGO_CMD: &str = "cargo.cmd";
+#[cfg(not(windows))]
+pub(crate) static CARGO_CMD: &str = "cargo";
+
+/// Folder inside a working puzzle where Rukata keeps its own files.
+pub(crate) static RUKATA_FOLDER: &str = ".rukata";
+
+// Cargo subcommands that build the attempt and so take the extra arguments from the settings.
+static BUILD_SUBCOMMANDS: &[&str] = &["build", "test", "run"];
+
+// How often a cargo run with a timeout is checked for completion.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Limits applied to a cargo run, a value of zero means no limit.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Limits {
+    /// Wall-clock time in seconds.
+    pub(crate) timeout: u64,
+    /// Memory in MiB for each process, only applied on Linux.
+    pub(crate) memory_limit: u64,
+    /// CPU time in seconds for each process, only applied on Linux.
+    pub(crate) cpu_limit: u64,
+}
+
+/// The reason a cargo run was stopped before it finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "reason", content = "limit", rename_all = "snake_case")]
+pub(crate) enum StopReason {
+    Timeout(u64),
+    MemoryLimit(u64),
+    CpuLimit(u64),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StopReason::Timeout(seconds) => {
+                write!(f, "it ran for longer than the {} second timeout", seconds)
+            }
+            StopReason::MemoryLimit(megabytes) => {
+                write!(f, "it used more than the {} MiB memory limit", megabytes)
+            }
+            StopReason::CpuLimit(seconds) => {
+                write!(f, "it used more than the {} second CPU time limit", seconds)
+            }
+        }
+    }
+}
+
+pub(crate) struct CargoOutput {
+    pub(crate) success: bool,
+    pub(crate) stdout: String,
+    pub(crate) stderr: String,
+    pub(crate) stopped: Option<StopReason>,
+    pub(crate) command_line: String,
+}
+
+/// Reads `reader` until it is closed, replacing invalid UTF-8 so that the rest is still read.
+fn read_lines<R: Read>(reader: R, stream: bool) -> String {
+    let mut reader = BufReader::new(reader);
+    let mut output = String::new();
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+
+        let line = String::from_utf8_lossy(&buffer);
+        let line = line.trim_end_matches(['\n', '\r']);
+        if stream {
+            eprintln!("{}", line);
+        }
+        output.push_str(line);
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(target_os = "linux")]
+fn create_command(program: &str, arguments: &[String], limits: &Limits) -> Command {
+    if limits.memory_limit == 0 && limits.cpu_limit == 0 {
+        let mut command = Command::new(program);
+        command.args(arguments);
+        return command;
+    }
+
+    // Setting the limits directly needs `unsafe`, so let the shell apply them instead.
+    let mut script = String::new();
+    if limits.memory_limit > 0 {
+        script.push_str(&format!("ulimit -v {} && ", limits.memory_limit * 1024));
+    }
+    if limits.cpu_limit > 0 {
+        script.push_str(&format!("ulimit -t {} && ", limits.cpu_limit));
+    }
+    script.push_str("exec \"$0\" \"$@\"");
+
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(script).arg(program).args(arguments);
+    command
+}
+
+#[cfg(not(target_os = "linux"))]
+fn create_command(program: &str, arguments: &[String], _limits: &Limits) -> Command {
+    let mut command = Command::new(program);
+    command.args(arguments);
+    command
+}
+
+#[cfg(unix)]
+mod process_group {
+    use nix::sys::signal::{killpg, Signal};
+    use nix::unistd::Pid;
+    use std::os::unix::process::CommandExt;
+    use std::process::{Child, Command};
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Once;
+
+    static RUNNING_GROUP: AtomicU32 = AtomicU32::new(0);
+    static INTERRUPT_HANDLER: Once = Once::new();
+
+    /// Starts the command in its own process group so the whole tree can be stopped.
+    pub(super) fn spawn(command: &mut Command) -> std::io::Result<Child> {
+        // The group no longer receives Ctrl+C from the terminal, so pass it on.
+        INTERRUPT_HANDLER.call_once(|| {
+            let _ = ctrlc::set_handler(|| {
+                let group = RUNNING_GROUP.load(Ordering::SeqCst);
+                if group != 0 {
+                    let _ = killpg(Pid::from_raw(group as i32), Signal::SIGINT);
+                }
+                std::process::exit(130);
+            });
+        });
+
+        let child = command.process_group(0).spawn()?;
+        RUNNING_GROUP.store(child.id(), Ordering::SeqCst);
+        Ok(child)
+    }
+
+    pub(super) fn finished() {
+        RUNNING_GROUP.store(0, Ordering::SeqCst);
+    }
+
+    /// Kills cargo and every process it started, such as the test binaries.
+    pub(super) fn kill(child: &mut Child) {
+        if killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL).is_err() {
+            let _ = child.kill();
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod process_group {
+    use std::process::{Child, Command};
+
+    pub(super) fn spawn(command: &mut Command) -> std::io::Result<Child> {
+        command.spawn()
+    }
+
+    pub(super) fn finished() {}
+
+    pub(super) fn kill(child: &mut Child) {
+        let _ = child.kill();
+    }
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: u64) -> std::io::Result<(bool, bool)> {
+    if timeout == 0 {
+        return child.wait().map(|status| (status.success(), false));
+    }
+
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok((status.success(), false));
+        }
+
+        if Instant::now() >= deadline {
+            process_group::kill(child);
+            child.wait()?;
+            return Ok((false, true));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Finds which of the resource limits stopped the run from its output.
+fn get_limit_stop_reason(limits: &Limits, stdout: &str, stderr: &str) -> Option<StopReason> {
+    if limits.memory_limit > 0
+        && (stdout.contains("memory allocation of") || stderr.contains("memory allocation of"))
+    {
+        return Some(StopReason::MemoryLimit(limits.memory_limit));
+    }
+
+    if limits.cpu_limit > 0 && stderr.contains("SIGXCPU") {
+        return Some(StopReason::CpuLimit(limits.cpu_limit));
+    }
+
+    None
+}
+
+/// Adds the toolchain, offline flag and extra arguments from the settings to the cargo arguments.
+///
+/// The toolchain and offline flag go before the subcommand as they apply to cargo itself. The
+/// toolchain is only added when cargo is the one on the `PATH`, which rustup manages. The extra
+/// arguments are only given to the subcommands that build the attempt, after the arguments of the
+/// subcommand but before any `--` separating the arguments passed on to the tests.
+fn get_cargo_arguments(arguments: &[&str], cargo_settings: &CargoSettings) -> Vec<String> {
+    let mut cargo_arguments = Vec::new();
+    if let (Some(toolchain), None) = (cargo_settings.get_toolchain(), cargo_settings.get_binary()) {
+        cargo_arguments.push(format!("+{}", toolchain));
+    }
+    if cargo_settings.is_offline() {
+        cargo_arguments.push("--offline".to_string());
+    }
+
+    let separator = arguments
+        .iter()
+        .position(|argument| *argument == "--")
+        .unwrap_or(arguments.len());
+    let (subcommand_arguments, tool_arguments) = arguments.split_at(separator);
+    cargo_arguments.extend(
+        subcommand_arguments
+            .iter()
+            .map(|argument| argument.to_string()),
+    );
+    if subcommand_arguments
+        .first()
+        .is_some_and(|subcommand| BUILD_SUBCOMMANDS.contains(subcommand))
+    {
+        cargo_arguments.extend(cargo_settings.get_arguments().iter().cloned());
+    }
+    cargo_arguments.extend(tool_arguments.iter().map(|argument| argument.to_string()));
+
+    cargo_arguments
+}
+
+/// Runs cargo in the given directory capturing its output.
+///
+/// When `stream` is set the build progress written to stderr is shown as it happens.
+/// The run is stopped once it goes over the timeout in `limits`.
+pub(crate) fn run_cargo(
+    arguments: &[&str],
+    directory: &Utf8Path,
+    cargo_settings: &CargoSettings,
+    stream: bool,
+    limits: &Limits,
+) -> Result<CargoOutput, RukataError> {
+    let program = match cargo_settings.get_binary() {
+        Some(binary) => binary.as_str(),
+        None => CARGO_CMD,
+    };
+    let arguments = get_cargo_arguments(arguments, cargo_settings);
+
+    let mut command = create_command(program, &arguments, limits);
+    command.current_dir(directory);
+
+    // Another cargo binary may not be a rustup proxy, which would reject `+toolchain`, so the
+    // toolchain is passed in the environment that a rustup proxy reads and any other cargo ignores.
+    if let (Some(toolchain), Some(_)) =
+        (cargo_settings.get_toolchain(), cargo_settings.get_binary())
+    {
+        command.env("RUSTUP_TOOLCHAIN", toolchain);
+    }
+    command.envs(cargo_settings.get_environment());
+
+    if let Some(target_directory) = cargo_settings.get_target_directory() {
+        command.env("CARGO_TARGET_DIR", target_directory);
+    }
+
+    // Keep cargo's colours when the progress is shown to the user.
+    if stream && Term::stderr().features().colors_supported() {
+        command.env("CARGO_TERM_COLOR", "always");
+    }
+
+    let command_line = format!("{} {}", program, arguments.join(" "));
+    run_command(command, command_line, None, stream, limits)
+}
+
+/// Runs a program built from the attempt, writing `stdin` to it and capturing its output.
+pub(crate) fn run_program(
+    program: &Utf8Path,
+    arguments: &[&str],
+    directory: &Utf8Path,
+    stdin: &str,
+    limits: &Limits,
+) -> Result<CargoOutput, RukataError> {
+    let arguments: Vec<String> = arguments
+        .iter()
+        .map(|argument| argument.to_string())
+        .collect();
+
+    let mut command = create_command(program.as_str(), &arguments, limits);
+    command.current_dir(directory);
+
+    let command_line = format!("{} {}", program, arguments.join(" "));
+    run_command(
+        command,
+        command_line,
+        Some(stdin.to_string()),
+        false,
+        limits,
+    )
+}
+
+/// Runs a command capturing its output, a command given no `stdin` reads from an empty input
+/// rather than waiting on the terminal.
+fn run_command(
+    mut command: Command,
+    command_line: String,
+    stdin: Option<String>,
+    stream: bool,
+    limits: &Limits,
+) -> Result<CargoOutput, RukataError> {
+    command
+        .stdin(if stdin.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = process_group::spawn(&mut command).map_err(|e| {
+        RukataError::Io(format!(
+            "Failed to run `{}` with error: {}",
+            command_line, e
+        ))
+    })?;
+
+    // A program that stops reading early closes its end of the pipe, which is not an error here.
+    let stdin_thread = child
+        .stdin
+        .take()
+        .zip(stdin)
+        .map(|(mut child_stdin, stdin)| {
+            thread::spawn(move || {
+                let _ = child_stdin.write_all(stdin.as_bytes());
+            })
+        });
+
+    let stderr = child.stderr.take().expect("Failed to capture stderr");
+    let stderr_thread = thread::spawn(move || read_lines(stderr, stream));
+
+    let stdout = child.stdout.take().expect("Failed to capture stdout");
+    let stdout_thread = thread::spawn(move || read_lines(stdout, false));
+
+    let result = wait_with_timeout(&mut child, limits.timeout);
+    process_group::finished();
+    let (success, timed_out) = result.map_err(|e| {
+        RukataError::Io(format!(
+            "Failed to run `{}` with error: {}",
+            command_line, e
+        ))
+    })?;
+
+    if let Some(stdin_thread) = stdin_thread {
+        let _ = stdin_thread.join();
+    }
+    let stdout = stdout_thread.join().unwrap_or_default();
+    let stderr = stderr_thread.join().unwrap_or_default();
+
+    let stopped = if timed_out {
+        Some(StopReason::Timeout(limits.timeout))
+    } else if !success {
+        get_limit_stop_reason(limits, &stdout, &stderr)
+    } else {
+        None
+    };
+
+    Ok(CargoOutput {
+        success,
+        stdout,
+        stderr,
+        stopped,
+        command_line,
+    })
+}
+
+/// Saves the output of a cargo run inside the working puzzle folder.
+pub(crate) fn write_log(
+    directory: &Utf8Path,
+    name: &str,
+    output: &CargoOutput,
+) -> Result<Utf8PathBuf, RukataError> {
+    let log_directory = directory.join(RUKATA_FOLDER);
+    if let Err(e) = fs::create_dir_all(&log_directory) {
+        return Err(RukataError::Io(format!(
+            "Failed to create directory `{}` with error: {}",
+            log_directory, e
+        )));
+    }
+
+    let log = format!(
+        "$ {}\n\n---- stderr ----\n{}\n---- stdout ----\n{}",
+        output.command_line,
+        console::strip_ansi_codes(&output.stderr),
+        console::strip_ansi_codes(&output.stdout)
+    );
+
+    let log_path = log_directory.join(name);
+    if let Err(e) = fs::write(&log_path, log) {
+        return Err(RukataError::Io(format!(
+            "Failed to create file `{}` with error: {}",
+            log_path, e
+        )));
+    }
+
+    Ok(log_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_cargo_settings(
+        toolchain: Option<&str>,
+        offline: bool,
+        arguments: &[&str],
+    ) -> CargoSettings {
+        let mut cargo_settings = CargoSettings::default();
+        cargo_settings.set_toolchain(toolchain.map(str::to_string));
+        cargo_settings.set_offline(offline);
+        cargo_settings.set_arguments(
+            arguments
+                .iter()
+                .map(|argument| argument.to_string())
+                .collect(),
+        );
+        cargo_settings
+    }
+
+    #[cfg(unix)]
+    fn spawn(program: &str, arguments: &[&str]) -> Child {
+        let mut command = Command::new(program);
+        command.args(arguments);
+        process_group::spawn(&mut command).unwrap()
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_wait_with_timeout() {
+        let mut child = spawn("sh", &["-c", "exit 0"]);
+        assert_eq!(wait_with_timeout(&mut child, 0).unwrap(), (true, false));
+
+        let mut child = spawn("sh", &["-c", "exit 1"]);
+        assert_eq!(wait_with_timeout(&mut child, 10).unwrap(), (false, false));
+
+        // A run going over the timeout is killed as soon as it does.
+        let start = Instant::now();
+        let mut child = spawn("sleep", &["30"]);
+        assert_eq!(wait_with_timeout(&mut child, 1).unwrap(), (false, true));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_read_lines() {
+        assert_eq!(
+            read_lines(&b"first\r\nsecond"[..], false),
+            "first\nsecond\n"
+        );
+
+        // Invalid UTF-8 is replaced instead of stopping the rest from being read.
+        assert_eq!(
+            read_lines(&b"bad \xff line\nnext line\n"[..], false),
+            "bad \u{FFFD} line\nnext line\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_without_stdin() {
+        // `cat` would wait on the terminal if it inherited stdin.
+        let limits = Limits {
+            timeout: 10,
+            ..Limits::default()
+        };
+        let output = run_command(Command::new("cat"), "cat".to_string(), None, false, &limits);
+        let output = output.unwrap();
+        assert!(output.success);
+        assert_eq!(output.stopped, None);
+        assert_eq!(output.stdout, "");
+    }
+
+    #[test]
+    fn test_get_limit_stop_reason() {
+        let limits = Limits {
+            timeout: 60,
+            memory_limit: 512,
+            cpu_limit: 30,
+        };
+        let out_of_memory = "memory allocation of 1073741824 bytes failed";
+        assert_eq!(
+            get_limit_stop_reason(&limits, "", out_of_memory),
+            Some(StopReason::MemoryLimit(512))
+        );
+        assert_eq!(
+            get_limit_stop_reason(&limits, out_of_memory, ""),
+            Some(StopReason::MemoryLimit(512))
+        );
+        assert_eq!(
+            get_limit_stop_reason(
+                &limits,
+                "",
+                "process didn't exit successfully (signal: 24, SIGXCPU: CPU time limit exceeded)"
+            ),
+            Some(StopReason::CpuLimit(30))
+        );
+        assert_eq!(
+            get_limit_stop_reason(&limits, "test result: FAILED", ""),
+            None
+        );
+
+        // Without a limit the same output is an ordinary failure.
+        assert_eq!(
+            get_limit_stop_reason(&Limits::default(), "", out_of_memory),
+            None
+        );
+        assert_eq!(
+            get_limit_stop_reason(&Limits::default(), "", "SIGXCPU"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_get_cargo_arguments() {
+        let cargo_settings = CargoSettings::default();
+        assert_eq!(
+            get_cargo_arguments(&["test", "--no-fail-fast"], &cargo_settings),
+            vec!["test", "--no-fail-fast"]
+        );
+
+        // The toolchain and offline flag apply to cargo, the extra arguments to the subcommand.
+        let cargo_settings = get_cargo_settings(Some("stable"), true, &["--jobs", "2"]);
+        assert_eq!(
+            get_cargo_arguments(&["test", "--no-fail-fast"], &cargo_settings),
+            vec![
+                "+stable",
+                "--offline",
+                "test",
+                "--no-fail-fast",
+                "--jobs",
+                "2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_cargo_arguments_subcommands() {
+        // Only the subcommands that build the attempt take the extra arguments.
+        let cargo_settings = get_cargo_settings(None, true, &["--features", "extra"]);
+        assert_eq!(
+            get_cargo_arguments(&["fmt", "--check"], &cargo_settings),
+            vec!["--offline", "fmt", "--check"]
+        );
+        assert_eq!(
+            get_cargo_arguments(&["clippy", "--all-targets"], &cargo_settings),
+            vec!["--offline", "clippy", "--all-targets"]
+        );
+        assert_eq!(
+            get_cargo_arguments(&["build"], &cargo_settings),
+            vec!["--offline", "build", "--features", "extra"]
+        );
+        assert_eq!(
+            get_cargo_arguments(&["run", "--release"], &cargo_settings),
+            vec!["--offline", "run", "--release", "--features", "extra"]
+        );
+    }
+
+    #[test]
+    fn test_get_cargo_arguments_binary() {
+        // A cargo binary set in the settings gets the toolchain from the environment instead.
+        let mut cargo_settings = get_cargo_settings(Some("stable"), false, &[]);
+        cargo_settings.set_binary(Some(Utf8PathBuf::from("/opt/cargo/bin/cargo")));
+        assert_eq!(
+            get_cargo_arguments(&["test"], &cargo_settings),
+            vec!["test"]
+        );
+    }
+
+    #[test]
+    fn test_get_cargo_arguments_separator() {
+        // Arguments after `--` are passed on to the tests and stay last.
+        let cargo_settings = get_cargo_settings(None, false, &["--jobs", "2"]);
+        assert_eq!(
+            get_cargo_arguments(
+                &["test", "--no-fail-fast", "--", "--exact", "add_test"],
+                &cargo_settings
+            ),
+            vec![
+                "test",
+                "--no-fail-fast",
+                "--jobs",
+                "2",
+                "--",
+                "--exact",
+                "add_test"
+            ]
+        );
+    }
+}
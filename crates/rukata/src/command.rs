@@ -1,6 +1,9 @@
-use crate::common::{print_red_title, print_white};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
 use camino::Utf8PathBuf;
+use rukata_progress::ProgressHandler;
 use rukata_settings::SettingsHandler;
+use std::process::ExitCode;
 
 fn get_config_path() -> Result<Utf8PathBuf, String> {
     let config = dirs::config_dir();
@@ -29,33 +32,47 @@ fn get_settings_file_path() -> Result<Utf8PathBuf, String> {
     }
 }
 
-pub(crate) fn get_settings() -> Result<SettingsHandler, String> {
-    SettingsHandler::new(get_settings_file_path()?)
+fn get_progress_file_path() -> Result<Utf8PathBuf, String> {
+    match get_config_path() {
+        Ok(path) => Ok(path.join("progress.json")),
+        Err(e) => Err(e),
+    }
+}
+
+pub(crate) fn get_settings() -> Result<SettingsHandler, RukataError> {
+    get_settings_file_path()
+        .and_then(SettingsHandler::new)
+        .map_err(RukataError::InvalidSettings)
+}
+
+pub(crate) fn get_progress() -> Result<ProgressHandler, RukataError> {
+    get_progress_file_path()
+        .and_then(ProgressHandler::new)
+        .map_err(RukataError::Io)
 }
 
 pub trait Command {
     fn set_settings(&mut self, settings: SettingsHandler);
     fn initialize(&mut self);
-    fn execute(&mut self);
-    fn get_errors(&self) -> Vec<String>;
+    fn execute(&mut self, reporter: &mut Reporter);
+    fn get_errors(&self) -> Vec<RukataError>;
 }
 
 pub struct CommandHandler {
     command: Box<dyn Command>,
+    reporter: Reporter,
 }
 
 impl CommandHandler {
-    pub fn new(command: Box<dyn Command>) -> Self {
-        Self { command }
+    pub fn new(command: Box<dyn Command>, reporter: Reporter) -> Self {
+        Self { command, reporter }
     }
 
-    pub fn run(&mut self) {
+    /// Runs the command returning the exit code of the first error.
+    pub fn run(&mut self) -> ExitCode {
         let settings_handler = match get_settings() {
             Ok(handler) => handler,
-            Err(e) => {
-                println!("{}", e);
-                return;
-            }
+            Err(e) => return self.finish(&[e]),
         };
 
         self.command.set_settings(settings_handler.clone());
@@ -63,20 +80,19 @@ impl CommandHandler {
         self.command.initialize();
         let errors = self.command.get_errors();
         if !errors.is_empty() {
-            print_red_title("The following errors occurred:");
-            for error in errors {
-                print_white(format!("- {}", error));
-            }
-            return;
+            return self.finish(&errors);
         }
 
-        self.command.execute();
-        let errors = self.command.get_errors();
-        if !errors.is_empty() {
-            print_red_title("The following errors occurred:");
-            for error in errors {
-                print_white(format!("- {}", error));
-            }
+        self.command.execute(&mut self.reporter);
+        self.finish(&self.command.get_errors())
+    }
+
+    fn finish(&self, errors: &[RukataError]) -> ExitCode {
+        self.reporter.finish(errors);
+
+        match errors.first() {
+            Some(error) => ExitCode::from(error.get_exit_code()),
+            None => ExitCode::SUCCESS,
         }
     }
 }
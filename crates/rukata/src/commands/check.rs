@@ -1,20 +1,49 @@
 use crate::argument_builder::GeneralArguments;
+use crate::cargo::{
+    run_cargo, run_program, write_log, CargoOutput, Limits, StopReason, RUKATA_FOLDER,
+};
 use crate::command::Command;
-use crate::common::print_green;
+use crate::common::{
+    copy_directory, format_duration, format_timestamp, generate_file, get_current_stage,
+    get_puzzle_folder_name, populate_stage, remove_stage_files, update_progress,
+};
+use crate::diagnostics::{parse_diagnostics, parse_executables, Diagnostic};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::test_report::{count_status, parse_test_output, TestResult, TestStatus};
 use crate::validation::validate_settings;
-use rukata_puzzle_data::{get_file_data, PuzzleData};
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use rukata_progress::versions::v1::{CheckResult, TimedRun};
+use rukata_puzzle_data::{get_file_data, CheckStep, CompileFailSnippet, PuzzleData, PuzzleKind};
+use rukata_settings::versions::v1::{CargoSettings, Settings};
 use rukata_settings::SettingsHandler;
+use serde::Serialize;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
+use tempfile::TempDir;
 
-#[cfg(windows)]
-static CARGO_CMD: &str = "cargo.cmd";
-#[cfg(not(windows))]
-static CARGO_CMD: &str = "cargo";
+static BUILD_LOG_NAME: &str = "build.log";
+static CHECK_LOG_NAME: &str = "check.log";
+static CLIPPY_LOG_NAME: &str = "clippy.log";
+static FORMAT_LOG_NAME: &str = "fmt.log";
+
+// Folder inside the target directory used to build copies of the attempt.
+static COPY_TARGET_FOLDER: &str = "rukata-copy";
+
+// Puzzles without check steps only run their tests.
+static DEFAULT_CHECK_STEPS: &[CheckStep] = &[CheckStep::Test { filter: None }];
+
+// Seconds `cargo test` may run for when neither the puzzle nor the settings set a timeout.
+const DEFAULT_TEST_TIMEOUT: u64 = 60;
 
 pub struct CheckCommand {
     arguments: GeneralArguments,
     settings: Option<SettingsHandler>,
-    errors: Vec<String>,
+    errors: Vec<RukataError>,
 }
 
 impl Command for CheckCommand {
@@ -24,7 +53,7 @@ impl Command for CheckCommand {
 
     fn initialize(&mut self) {}
 
-    fn execute(&mut self) {
+    fn execute(&mut self, reporter: &mut Reporter) {
         let settings_handler = self.settings.as_ref().expect("Failed to set settings");
 
         // Get the settings
@@ -39,82 +68,1540 @@ impl Command for CheckCommand {
         let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
             Some(data) => data,
             None => {
-                self.errors
-                    .push(format!("Puzzle ID is not valid {}", puzzle_id));
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
                 return;
             }
         };
 
         // Get folder path.
-        let title = puzzle_data.get_title();
-        let folder_name = format!("p{:0>5} - {}", puzzle_id, title);
+        let folder_name = get_puzzle_folder_name(puzzle_data);
         let directory = settings.get_directory().join("working").join(folder_name);
 
+        reporter.set("puzzle_id", puzzle_id);
+        reporter.set("directory", &directory);
+
         // Check folder existing.
         if !directory.exists() {
-            self.errors
-                .push(format!("Directory `{}` does not exist", directory));
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` does not exist",
+                directory
+            )));
         }
 
         if !self.errors.is_empty() {
             return;
         }
 
-        // Check files.
-        let read_only_files = puzzle_data.get_read_only_files();
-        for file_data in read_only_files {
-            let file_path = directory.join(file_data.get_relative_path());
-            if !file_path.exists() {
-                self.errors
-                    .push(format!("File `{}` does not exist", file_path));
-            } else {
-                match fs::read(&file_path) {
-                    Ok(file_content) => {
-                        if !file_data.check_data(&file_content) {
-                            self.errors
-                                .push(format!("File `{}` does not match stored data", file_path));
-                        }
+        let stage = match get_current_stage(puzzle_data) {
+            Ok(stage) => stage,
+            Err(error) => {
+                self.errors.push(error);
+                return;
+            }
+        };
+        if puzzle_data.get_stage_count() > 1 {
+            reporter.set("stage", stage + 1);
+            reporter.set("stage_count", puzzle_data.get_stage_count());
+            reporter.print_cyan_title(format!(
+                "Checking stage {} of {}",
+                stage + 1,
+                puzzle_data.get_stage_count()
+            ));
+        }
+
+        // Run the checks and record the attempt.
+        // Cargo output is only streamed to people, never into JSON output.
+        let report = run_check(
+            puzzle_data,
+            &directory,
+            settings,
+            stage,
+            reporter.is_human(),
+        );
+        print_check_report(reporter, &report, puzzle_data);
+        reporter.set("diagnostics", &report.diagnostics);
+        reporter.set("tests", &report.tests);
+        reporter.set("hidden_tests", &report.hidden_tests);
+        reporter.set("steps", &report.steps);
+        reporter.set("compile_fail", &report.compile_fail);
+        reporter.set("mutants", &report.mutants);
+        reporter.set("stopped", report.stopped);
+        reporter.set("lints", &report.lints);
+        reporter.set("formatting", &report.formatting);
+        reporter.set("log_paths", &report.log_paths);
+        reporter.set(
+            "explanations",
+            report
+                .diagnostics
+                .iter()
+                .chain(&report.lints)
+                .filter_map(|diagnostic| diagnostic.get_code())
+                .filter_map(|code| Some((code, puzzle_data.get_error_explanation(code)?)))
+                .collect::<BTreeMap<&str, &str>>(),
+        );
+
+        self.errors = report.errors;
+        let (run_time, unlocked) = finish_check(puzzle_data, &directory, stage, &mut self.errors);
+        reporter.set("time", run_time);
+        reporter.set("unlocked", &unlocked);
+
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        if let Some(unlocked) = unlocked {
+            reporter.print_green(format!(
+                "You have passed stage {} of {} of puzzle {}",
+                stage + 1,
+                unlocked.stage_count,
+                puzzle_id
+            ));
+            reporter.print_cyan_title(unlocked.to_string());
+            reporter.print_white(&unlocked.description);
+            for file in &unlocked.files {
+                reporter.print_white(format!("Added `{}`", file));
+            }
+            return;
+        }
+
+        match run_time {
+            Some(run_time) => {
+                reporter.print_green(format!(
+                    "You have completed puzzle {} {}",
+                    puzzle_id, run_time
+                ));
+                if let Some(next_review) = run_time.next_review {
+                    reporter.print_white(format!(
+                        "Puzzle {} is due for review on {}",
+                        puzzle_id,
+                        format_timestamp(Some(&next_review))
+                    ));
+                }
+            }
+            None => reporter.print_green(format!("You have completed puzzle {}", puzzle_id)),
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum StepStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+impl fmt::Display for StepStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StepStatus::Passed => f.pad("ok"),
+            StepStatus::Failed => f.pad("FAILED"),
+            StepStatus::Skipped => f.pad("skipped"),
+        }
+    }
+}
+
+/// The outcome of a check step, with the differences when the output of a binary does not match.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct StepResult {
+    pub(crate) step: String,
+    pub(crate) status: StepStatus,
+    pub(crate) output_diff: Option<String>,
+}
+
+/// The outcome of building a compile-fail snippet.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct CompileFailResult {
+    pub(crate) path: String,
+    pub(crate) code: String,
+    pub(crate) message_pattern: Option<String>,
+    pub(crate) passed: bool,
+    /// The errors the snippet failed with, empty when it compiled.
+    pub(crate) errors: Vec<Diagnostic>,
+}
+
+impl CompileFailResult {
+    fn new(snippet: &CompileFailSnippet, errors: Vec<Diagnostic>) -> Self {
+        let pattern = snippet.get_message_pattern().map(|pattern| {
+            Regex::new(pattern).expect("Compile-fail message patterns are checked by the build")
+        });
+        let passed = errors.iter().any(|diagnostic| {
+            diagnostic.get_code() == Some(snippet.get_code())
+                && pattern
+                    .as_ref()
+                    .map_or(true, |pattern| pattern.is_match(&diagnostic.message))
+        });
+
+        Self {
+            path: snippet.get_path().to_string(),
+            code: snippet.get_code().to_string(),
+            message_pattern: snippet.get_message_pattern().map(str::to_string),
+            passed,
+            errors,
+        }
+    }
+
+    /// The error the snippet has to fail with, such as ``error[E0382] matching `^use of` ``.
+    fn get_expected(&self) -> String {
+        match &self.message_pattern {
+            Some(pattern) => format!("error[{}] matching `{}`", self.code, pattern),
+            None => format!("error[{}]", self.code),
+        }
+    }
+}
+
+/// How the tests of a write-the-tests puzzle caught a mutant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "by", rename_all = "snake_case")]
+pub(crate) enum MutantCatch {
+    /// The tests do not compile against the mutant.
+    CompileError,
+    /// Tests failed against the mutant.
+    FailedTests { failed: usize },
+    /// The tests were stopped by a limit, such as a mutant that never returns.
+    Stopped { stopped: StopReason },
+}
+
+impl fmt::Display for MutantCatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MutantCatch::CompileError => write!(f, "the tests do not compile"),
+            MutantCatch::FailedTests { failed } => write!(f, "{} test(s) failed", failed),
+            MutantCatch::Stopped { stopped } => write!(f, "stopped because {}", stopped),
+        }
+    }
+}
+
+/// Whether the tests of a write-the-tests puzzle caught a mutant.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct MutantResult {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    pub(crate) caught: bool,
+    pub(crate) caught_by: Option<MutantCatch>,
+}
+
+/// How long a finished timed run took, compared with the personal best before it.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub(crate) struct RunTime {
+    pub(crate) active_seconds: u64,
+    pub(crate) previous_best_seconds: Option<u64>,
+    pub(crate) new_personal_best: bool,
+    /// When the finished run schedules the puzzle to be reviewed again.
+    pub(crate) next_review: Option<DateTime<Utc>>,
+}
+
+impl RunTime {
+    fn new(
+        active_seconds: u64,
+        previous_best_seconds: Option<u64>,
+        next_review: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            active_seconds,
+            previous_best_seconds,
+            new_personal_best: previous_best_seconds.map_or(true, |best| active_seconds < best),
+            next_review,
+        }
+    }
+}
+
+impl fmt::Display for RunTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "in an estimated {} of active time",
+            format_duration(self.active_seconds)
+        )?;
+        match self.previous_best_seconds {
+            Some(best) if self.new_personal_best => {
+                write!(f, ", a new personal best beating {}", format_duration(best))
+            }
+            Some(best) => write!(f, ", the personal best is {}", format_duration(best)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The next stage of a multi-stage puzzle, unlocked by a check passing the stage before it.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct UnlockedStage {
+    /// The stage unlocked, counting from 1.
+    pub(crate) stage: usize,
+    pub(crate) stage_count: usize,
+    pub(crate) name: String,
+    pub(crate) title: String,
+    pub(crate) description: String,
+    /// Files added to the attempt for the stage.
+    pub(crate) files: Vec<Utf8PathBuf>,
+}
+
+impl fmt::Display for UnlockedStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unlocked stage {} of {}: {}",
+            self.stage, self.stage_count, self.title
+        )
+    }
+}
+
+#[derive(Default, Serialize)]
+pub(crate) struct CheckReport {
+    pub(crate) errors: Vec<RukataError>,
+    pub(crate) diagnostics: Vec<Diagnostic>,
+    pub(crate) tests: Vec<TestResult>,
+    pub(crate) hidden_tests: Vec<TestResult>,
+    pub(crate) steps: Vec<StepResult>,
+    pub(crate) compile_fail: Vec<CompileFailResult>,
+    pub(crate) mutants: Vec<MutantResult>,
+    pub(crate) stopped: Option<StopReason>,
+    pub(crate) lints: Vec<Diagnostic>,
+    pub(crate) formatting: Option<String>,
+    pub(crate) log_paths: Vec<Utf8PathBuf>,
+}
+
+/// Limits for running the tests, the puzzle timeout takes priority over the settings.
+fn get_test_limits(puzzle_data: &PuzzleData, settings: &Settings) -> Limits {
+    Limits {
+        timeout: puzzle_data
+            .get_timeout()
+            .or(settings.get_test_timeout())
+            .unwrap_or(DEFAULT_TEST_TIMEOUT),
+        memory_limit: settings.get_memory_limit().unwrap_or_default(),
+        cpu_limit: settings.get_cpu_limit().unwrap_or_default(),
+    }
+}
+
+/// Checks the read-only files are untouched, runs the check steps and then the puzzle gates.
+///
+/// Only the read-only files unlocked by `stage` are checked, and the hidden tests are only run
+/// on the last stage. When `stream` is set the cargo build progress is shown as it happens.
+pub(crate) fn run_check(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    stage: usize,
+    stream: bool,
+) -> CheckReport {
+    let mut report = CheckReport::default();
+
+    // Check files.
+    let read_only_files = puzzle_data.get_stage_read_only_files(stage);
+    for file_data in read_only_files {
+        let file_path = directory.join(file_data.get_relative_path());
+        if !file_path.exists() {
+            report.errors.push(RukataError::TamperedFile(format!(
+                "File `{}` does not exist",
+                file_path
+            )));
+        } else {
+            match fs::read(&file_path) {
+                Ok(file_content) => {
+                    if !file_data.check_data(&file_content) {
+                        report.errors.push(RukataError::TamperedFile(format!(
+                            "File `{}` does not match stored data",
+                            file_path
+                        )));
                     }
-                    Err(e) => {
-                        self.errors.push(format!(
-                            "Failed to read file `{}` with error: {} ",
-                            file_path, e
-                        ));
+                }
+                Err(e) => {
+                    report.errors.push(RukataError::Io(format!(
+                        "Failed to read file `{}` with error: {} ",
+                        file_path, e
+                    )));
+                }
+            }
+        }
+    }
+
+    if !report.errors.is_empty() {
+        return report;
+    }
+
+    // Build the tests first so compile errors can be reported clearly.
+    let arguments = ["test", "--no-run", "--message-format=json"];
+    match run_cargo(
+        &arguments,
+        directory,
+        settings.get_cargo(),
+        stream,
+        &Limits::default(),
+    ) {
+        Ok(output) => {
+            if !output.success {
+                report.diagnostics = parse_diagnostics(&output.stdout);
+                let log_path = write_log(directory, BUILD_LOG_NAME, &output);
+
+                // Cargo can fail before compiling anything, such as on an invalid manifest.
+                if report.diagnostics.is_empty() {
+                    let mut message = format!(
+                        "Running `{}` has failed without a compile error",
+                        output.command_line
+                    );
+                    if let Ok(log_path) = &log_path {
+                        message.push_str(&format!(", see `{}` for its output", log_path));
                     }
+                    report.errors.push(RukataError::CargoFailure(message));
+                } else {
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "The attempt does not compile, found {} error(s)",
+                        report.diagnostics.len()
+                    )));
                 }
+
+                match log_path {
+                    Ok(log_path) => report.log_paths.push(log_path),
+                    Err(e) => report.errors.push(e),
+                }
+                return report;
             }
         }
+        Err(e) => {
+            report.errors.push(e);
+            return report;
+        }
+    }
 
-        if !self.errors.is_empty() {
+    // Run the check steps in order, the steps after a failing one are skipped.
+    let check_steps = match puzzle_data.get_check_steps() {
+        [] => DEFAULT_CHECK_STEPS,
+        check_steps => check_steps,
+    };
+    let limits = get_test_limits(puzzle_data, settings);
+    for (index, check_step) in check_steps.iter().enumerate() {
+        let description = get_step_description(check_step);
+        if !report.errors.is_empty() {
+            report.steps.push(StepResult {
+                step: description,
+                status: StepStatus::Skipped,
+                output_diff: None,
+            });
+            continue;
+        }
+
+        let log_name = if check_steps.len() == 1 {
+            CHECK_LOG_NAME.to_string()
+        } else {
+            format!("check-{}.log", index + 1)
+        };
+        let step_run = StepRun {
+            directory,
+            settings,
+            stream,
+            limits: &limits,
+            description: &description,
+            log_name: &log_name,
+        };
+
+        let error_count = report.errors.len();
+        let output_diff = run_check_step(check_step, &step_run, &mut report);
+        let status = if report.errors.len() == error_count {
+            StepStatus::Passed
+        } else {
+            StepStatus::Failed
+        };
+        report.steps.push(StepResult {
+            step: description,
+            status,
+            output_diff,
+        });
+    }
+
+    if report.errors.is_empty() {
+        run_compile_fail(puzzle_data, directory, settings, stream, &mut report);
+    }
+
+    if report.errors.is_empty() {
+        run_mutants(puzzle_data, directory, settings, stream, &mut report);
+    }
+
+    if report.errors.is_empty() && stage + 1 == puzzle_data.get_stage_count() {
+        run_hidden_tests(puzzle_data, directory, settings, &mut report);
+    }
+
+    // Grade the style of the attempt once it works.
+    if report.errors.is_empty() {
+        run_clippy(puzzle_data, directory, settings, stream, &mut report);
+        run_fmt(puzzle_data, directory, settings, stream, &mut report);
+    }
+
+    report
+}
+
+/// Shared details for running a single check step.
+struct StepRun<'run> {
+    directory: &'run Utf8PathBuf,
+    settings: &'run Settings,
+    stream: bool,
+    limits: &'run Limits,
+    description: &'run str,
+    log_name: &'run str,
+}
+
+impl StepRun<'_> {
+    fn write_log(&self, output: &CargoOutput, report: &mut CheckReport) {
+        match write_log(self.directory, self.log_name, output) {
+            Ok(log_path) => report.log_paths.push(log_path),
+            Err(e) => report.errors.push(e),
+        }
+    }
+
+    /// Records the error for a run that was stopped, returning whether it was.
+    fn check_stopped(&self, output: &CargoOutput, report: &mut CheckReport) -> bool {
+        match output.stopped {
+            Some(stopped) => {
+                report.stopped = Some(stopped);
+                report.errors.push(RukataError::CargoFailure(format!(
+                    "The attempt was stopped because {}",
+                    stopped
+                )));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// The cargo command line a step runs, shown in the step results.
+fn get_step_description(check_step: &CheckStep) -> String {
+    let mut description = String::from("cargo");
+    match check_step {
+        CheckStep::Test { filter } => {
+            description.push_str(" test");
+            if let Some(filter) = filter {
+                description.push_str(&format!(" {}", filter));
+            }
+        }
+        CheckStep::Run { bin, arguments, .. } => {
+            description.push_str(" run");
+            if let Some(bin) = bin {
+                description.push_str(&format!(" --bin {}", bin));
+            }
+            if !arguments.is_empty() {
+                description.push_str(&format!(" -- {}", arguments.join(" ")));
+            }
+        }
+        CheckStep::DocTest => description.push_str(" test --doc"),
+        CheckStep::Build { features } => {
+            description.push_str(" build");
+            if !features.is_empty() {
+                description.push_str(&format!(" --features {}", features.join(",")));
+            }
+        }
+        CheckStep::Cargo { arguments } => {
+            description.push_str(&format!(" {}", arguments.join(" ")));
+        }
+    }
+
+    description
+}
+
+/// Runs a check step, returning the differences when the output of a binary does not match.
+fn run_check_step(
+    check_step: &CheckStep,
+    step_run: &StepRun,
+    report: &mut CheckReport,
+) -> Option<String> {
+    match *check_step {
+        CheckStep::Test { filter } => {
+            let mut arguments = vec!["test", "--no-fail-fast"];
+            arguments.extend(filter);
+            run_test_step(&arguments, step_run, report);
+        }
+        CheckStep::Run {
+            bin,
+            arguments,
+            stdin,
+            stdout,
+            stderr,
+        } => {
+            let program = build_binary(bin, step_run, report)?;
+            return run_binary_step(&program, arguments, stdin, stdout, stderr, step_run, report);
+        }
+        CheckStep::DocTest => run_test_step(&["test", "--doc"], step_run, report),
+        CheckStep::Build { features } => {
+            let features = features.join(",");
+            let mut arguments = vec!["build", "--message-format=json"];
+            if !features.is_empty() {
+                arguments.extend(["--features", &features]);
+            }
+            run_build_step(&arguments, step_run, report);
+        }
+        CheckStep::Cargo { arguments } => {
+            match run_cargo(
+                arguments,
+                step_run.directory,
+                step_run.settings.get_cargo(),
+                step_run.stream,
+                step_run.limits,
+            ) {
+                Ok(output) => {
+                    if !step_run.check_stopped(&output, report) && !output.success {
+                        report.errors.push(RukataError::CargoFailure(format!(
+                            "Run `{}` has failed",
+                            step_run.description
+                        )));
+                    }
+                    step_run.write_log(&output, report);
+                }
+                Err(e) => report.errors.push(e),
+            }
+        }
+    }
+
+    None
+}
+
+/// Runs the tests, adding each result to the report.
+fn run_test_step(arguments: &[&str], step_run: &StepRun, report: &mut CheckReport) {
+    match run_cargo(
+        arguments,
+        step_run.directory,
+        step_run.settings.get_cargo(),
+        step_run.stream,
+        step_run.limits,
+    ) {
+        Ok(output) => {
+            let tests = parse_test_output(&output.stdout);
+            let failed = count_status(&tests, TestStatus::Failed);
+            report.tests.extend(tests);
+
+            if !step_run.check_stopped(&output, report) && !output.success {
+                if failed > 0 {
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "Run `{}` has failed with {} failing test(s)",
+                        step_run.description, failed
+                    )));
+                } else {
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "Run `{}` has failed",
+                        step_run.description
+                    )));
+                }
+            }
+
+            step_run.write_log(&output, report);
+        }
+        Err(e) => report.errors.push(e),
+    }
+}
+
+/// Builds the attempt, adding the compiler errors to the report when it fails.
+///
+/// Returns the JSON lines written by cargo when the build succeeds.
+fn run_build_step(
+    arguments: &[&str],
+    step_run: &StepRun,
+    report: &mut CheckReport,
+) -> Option<String> {
+    match run_cargo(
+        arguments,
+        step_run.directory,
+        step_run.settings.get_cargo(),
+        step_run.stream,
+        &Limits::default(),
+    ) {
+        Ok(output) => {
+            if output.success {
+                return Some(output.stdout);
+            }
+
+            let diagnostics = parse_diagnostics(&output.stdout);
+            report.errors.push(RukataError::CargoFailure(format!(
+                "Run `{}` has failed, found {} error(s)",
+                step_run.description,
+                diagnostics.len()
+            )));
+            report.diagnostics.extend(diagnostics);
+            step_run.write_log(&output, report);
+        }
+        Err(e) => report.errors.push(e),
+    }
+
+    None
+}
+
+/// Builds the binary to run, which the puzzle has to pick when there are several.
+fn build_binary(
+    bin: Option<&str>,
+    step_run: &StepRun,
+    report: &mut CheckReport,
+) -> Option<Utf8PathBuf> {
+    let mut arguments = vec!["build", "--message-format=json"];
+    match bin {
+        Some(bin) => arguments.extend(["--bin", bin]),
+        None => arguments.push("--bins"),
+    }
+
+    let stdout = run_build_step(&arguments, step_run, report)?;
+    let mut executables = parse_executables(&stdout);
+    if executables.len() == 1 {
+        return executables.pop();
+    }
+
+    report.errors.push(RukataError::CargoFailure(format!(
+        "Run `{}` has failed, expected a single binary but found {}",
+        step_run.description,
+        executables.len()
+    )));
+    None
+}
+
+/// Runs a binary of the attempt and compares its output with the expected output.
+fn run_binary_step(
+    program: &Utf8Path,
+    arguments: &[&str],
+    stdin: &str,
+    expected_stdout: Option<&str>,
+    expected_stderr: Option<&str>,
+    step_run: &StepRun,
+    report: &mut CheckReport,
+) -> Option<String> {
+    let output = match run_program(
+        program,
+        arguments,
+        step_run.directory,
+        stdin,
+        step_run.limits,
+    ) {
+        Ok(output) => output,
+        Err(e) => {
+            report.errors.push(e);
+            return None;
+        }
+    };
+
+    step_run.write_log(&output, report);
+    if step_run.check_stopped(&output, report) {
+        return None;
+    }
+
+    if !output.success {
+        report.errors.push(RukataError::CargoFailure(format!(
+            "Run `{}` has failed, the binary did not exit successfully",
+            step_run.description
+        )));
+    }
+
+    let mut output_diff = String::new();
+    let mut mismatched = Vec::new();
+    for (name, expected, actual) in [
+        ("stdout", expected_stdout, &output.stdout),
+        ("stderr", expected_stderr, &output.stderr),
+    ] {
+        let Some(expected) = expected else {
+            continue;
+        };
+
+        // Trailing new lines are ignored as they are easy to miss in the config.
+        let expected = format!("{}\n", normalize_output(expected));
+        let actual = format!("{}\n", normalize_output(actual));
+        if expected != actual {
+            mismatched.push(name);
+            output_diff.push_str(
+                &TextDiff::from_lines(&expected, &actual)
+                    .unified_diff()
+                    .header(&format!("expected {}", name), &format!("actual {}", name))
+                    .to_string(),
+            );
+        }
+    }
+
+    if mismatched.is_empty() {
+        return None;
+    }
+
+    report.errors.push(RukataError::CargoFailure(format!(
+        "Run `{}` has failed, the {} does not match the expected output",
+        step_run.description,
+        mismatched.join(" and ")
+    )));
+    Some(output_diff)
+}
+
+fn normalize_output(output: &str) -> String {
+    output
+        .replace("\r\n", "\n")
+        .trim_end_matches('\n')
+        .to_string()
+}
+
+/// A copy of the attempt in a temporary directory, removed once it is dropped.
+struct AttemptCopy {
+    _temporary_directory: TempDir,
+    directory: Utf8PathBuf,
+    /// Builds into a target directory kept for copies so the dependencies are not compiled again.
+    cargo_settings: CargoSettings,
+}
+
+impl AttemptCopy {
+    fn new(directory: &Utf8Path, settings: &Settings) -> Result<Self, RukataError> {
+        let temporary_directory = tempfile::Builder::new()
+            .prefix("rukata-")
+            .tempdir()
+            .map_err(|e| {
+                RukataError::Io(format!(
+                    "Failed to create a temporary directory with error: {}",
+                    e
+                ))
+            })?;
+
+        let copy_path = match Utf8Path::from_path(temporary_directory.path()) {
+            Some(copy_path) => copy_path.to_path_buf(),
+            None => {
+                return Err(RukataError::Io(format!(
+                    "Temporary directory `{}` is not a UTF-8 path",
+                    temporary_directory.path().display()
+                )))
+            }
+        };
+
+        copy_directory(directory, &copy_path, &["target", RUKATA_FOLDER])?;
+
+        // Cargo names the build of a package the same wherever it is, so a copy sharing the
+        // target directory of the attempt would replace the build of the attempt.
+        let mut cargo_settings = settings.get_cargo().clone();
+        let target_directory = match cargo_settings.get_target_directory() {
+            Some(target_directory) => target_directory.join(COPY_TARGET_FOLDER),
+            None => directory.join("target").join(COPY_TARGET_FOLDER),
+        };
+        cargo_settings.set_target_directory(Some(target_directory));
+
+        Ok(Self {
+            _temporary_directory: temporary_directory,
+            directory: copy_path,
+            cargo_settings,
+        })
+    }
+
+    fn add_file(&self, relative_path: &str, data: &[u8]) -> Result<(), RukataError> {
+        match generate_file(self.directory.join(relative_path), data) {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Builds each snippet of a compile-fail puzzle on its own, each has to fail with its expected error.
+fn run_compile_fail(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    stream: bool,
+    report: &mut CheckReport,
+) {
+    if *puzzle_data.get_kind() != PuzzleKind::CompileFail {
+        return;
+    }
+
+    // The snippets are built as examples of a copy so they can use the puzzle crate.
+    let attempt_copy = match AttemptCopy::new(directory, settings) {
+        Ok(attempt_copy) => attempt_copy,
+        Err(e) => {
+            report.errors.push(e);
+            return;
+        }
+    };
+
+    let snippets = puzzle_data.get_compile_fail_snippets();
+    for snippet in snippets {
+        let file_path = directory.join(snippet.get_path());
+        let data = match fs::read(&file_path) {
+            Ok(data) => data,
+            Err(e) => {
+                report.errors.push(RukataError::Io(format!(
+                    "Failed to read file `{}` with error: {}",
+                    file_path, e
+                )));
+                return;
+            }
+        };
+
+        let example_path = format!("examples/{}.rs", get_snippet_name(snippet));
+        if let Err(e) = attempt_copy.add_file(&example_path, &data) {
+            report.errors.push(e);
             return;
         }
+    }
 
-        // Run cargo test.
-        let cargo_exit_status = std::process::Command::new(CARGO_CMD)
-            .args(["test"])
-            .current_dir(directory)
-            .output();
-        match cargo_exit_status {
+    for snippet in snippets {
+        let arguments = [
+            "build",
+            "--example",
+            get_snippet_name(snippet),
+            "--message-format=json",
+        ];
+        match run_cargo(
+            &arguments,
+            &attempt_copy.directory,
+            &attempt_copy.cargo_settings,
+            stream,
+            &Limits::default(),
+        ) {
             Ok(output) => {
-                if !output.status.success() {
-                    self.errors.push("Run `cargo test` has failed".to_string());
-                }
+                let errors = if output.success {
+                    Vec::new()
+                } else {
+                    parse_diagnostics(&output.stdout)
+                };
+                report
+                    .compile_fail
+                    .push(CompileFailResult::new(snippet, errors));
             }
             Err(e) => {
-                self.errors
-                    .push(format!("Failed to run `cargo test` with error: {} ", e));
+                report.errors.push(e);
+                return;
             }
         }
+    }
 
-        if !self.errors.is_empty() {
+    let failed = report
+        .compile_fail
+        .iter()
+        .filter(|result| !result.passed)
+        .count();
+    if failed > 0 {
+        report.errors.push(RukataError::CargoFailure(format!(
+            "{} of the compile-fail snippets did not fail with the expected error",
+            failed
+        )));
+    }
+}
+
+fn get_snippet_name(snippet: &CompileFailSnippet) -> &str {
+    Utf8Path::new(snippet.get_path())
+        .file_stem()
+        .unwrap_or_default()
+}
+
+/// Runs the tests of a write-the-tests puzzle against a copy of the attempt for each mutant.
+///
+/// Each copy is built without limits first, so a slow build is never mistaken for a caught
+/// mutant. Only failing tests, tests that do not compile or tests stopped by a limit catch it.
+fn run_mutants(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    stream: bool,
+    report: &mut CheckReport,
+) {
+    if *puzzle_data.get_kind() != PuzzleKind::WriteTests {
+        return;
+    }
+
+    // Tests failing against the given implementation would catch every mutant.
+    let limits = get_test_limits(puzzle_data, settings);
+    if !has_full_test_step(puzzle_data) {
+        match run_cargo(&["test"], directory, settings.get_cargo(), stream, &limits) {
+            Ok(output) if output.success => {}
+            Ok(_) => {
+                report.errors.push(RukataError::CargoFailure(
+                    "The tests have to pass against the given implementation before the mutants are run"
+                        .to_string(),
+                ));
+                return;
+            }
+            Err(e) => {
+                report.errors.push(e);
+                return;
+            }
+        }
+    }
+
+    for mutant in puzzle_data.get_mutants() {
+        let attempt_copy = match AttemptCopy::new(directory, settings) {
+            Ok(attempt_copy) => attempt_copy,
+            Err(e) => {
+                report.errors.push(e);
+                return;
+            }
+        };
+
+        for file_data in mutant.get_files() {
+            if let Err(e) =
+                attempt_copy.add_file(file_data.get_relative_path(), file_data.get_raw_data())
+            {
+                report.errors.push(e);
+                return;
+            }
+        }
+
+        let caught_by = match run_mutant(&attempt_copy, stream, &limits) {
+            Ok(caught_by) => caught_by,
+            Err(e) => {
+                report.errors.push(e);
+                return;
+            }
+        };
+        report.mutants.push(MutantResult {
+            name: mutant.get_name().to_string(),
+            description: mutant.get_description().to_string(),
+            caught: caught_by.is_some(),
+            caught_by,
+        });
+    }
+
+    let survived = report
+        .mutants
+        .iter()
+        .filter(|result| !result.caught)
+        .count();
+    if survived > 0 {
+        report.errors.push(RukataError::CargoFailure(format!(
+            "{} of the {} mutant(s) survived the tests",
+            survived,
+            report.mutants.len()
+        )));
+    }
+}
+
+/// Whether the check steps already run every test against the attempt.
+fn has_full_test_step(puzzle_data: &PuzzleData) -> bool {
+    let check_steps = match puzzle_data.get_check_steps() {
+        [] => DEFAULT_CHECK_STEPS,
+        check_steps => check_steps,
+    };
+
+    check_steps.contains(&CheckStep::Test { filter: None })
+}
+
+/// Builds and tests a copy of the attempt holding a mutant, returning how it was caught.
+fn run_mutant(
+    attempt_copy: &AttemptCopy,
+    stream: bool,
+    limits: &Limits,
+) -> Result<Option<MutantCatch>, RukataError> {
+    let output = run_cargo(
+        &["test", "--no-run", "--message-format=json"],
+        &attempt_copy.directory,
+        &attempt_copy.cargo_settings,
+        stream,
+        &Limits::default(),
+    )?;
+    if !output.success {
+        // Only the tests of the user may fail to compile, anything else is not caused by them.
+        let errors: Vec<Diagnostic> = parse_diagnostics(&output.stdout)
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .collect();
+        let in_tests = errors.iter().all(|diagnostic| {
+            diagnostic
+                .get_primary_span()
+                .is_some_and(|span| span.file_name.starts_with("tests/"))
+        });
+        if !errors.is_empty() && in_tests {
+            return Ok(Some(MutantCatch::CompileError));
+        }
+
+        return Err(RukataError::CargoFailure(format!(
+            "Building a mutant with `{}` has failed: {}",
+            output.command_line,
+            output.stderr.trim()
+        )));
+    }
+
+    let output = run_cargo(
+        &["test", "--no-fail-fast"],
+        &attempt_copy.directory,
+        &attempt_copy.cargo_settings,
+        stream,
+        limits,
+    )?;
+    if let Some(stopped) = output.stopped {
+        return Ok(Some(MutantCatch::Stopped { stopped }));
+    }
+
+    if output.success {
+        return Ok(None);
+    }
+
+    let failed = count_status(&parse_test_output(&output.stdout), TestStatus::Failed);
+    if failed == 0 {
+        return Err(RukataError::CargoFailure(format!(
+            "Testing a mutant with `{}` has failed without a failing test: {}",
+            output.command_line,
+            output.stderr.trim()
+        )));
+    }
+
+    Ok(Some(MutantCatch::FailedTests { failed }))
+}
+
+/// Runs the hidden tests against a copy of the attempt so they never appear in the working folder.
+///
+/// Cargo output is never streamed or logged as it would give the hidden tests away.
+fn run_hidden_tests(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    report: &mut CheckReport,
+) {
+    let hidden_tests = puzzle_data.get_hidden_tests();
+    if hidden_tests.is_empty() {
+        return;
+    }
+
+    // Copy the attempt and add the hidden tests.
+    let attempt_copy = match AttemptCopy::new(directory, settings) {
+        Ok(attempt_copy) => attempt_copy,
+        Err(e) => {
+            report.errors.push(e);
+            return;
+        }
+    };
+
+    for file_data in hidden_tests {
+        if let Err(e) =
+            attempt_copy.add_file(file_data.get_relative_path(), file_data.get_raw_data())
+        {
+            report.errors.push(e);
             return;
         }
+    }
 
-        print_green(format!("You have completed puzzle {}", puzzle_id))
+    // Only the hidden test targets are run, the visible tests have already passed.
+    let mut arguments = vec!["test", "--no-fail-fast"];
+    for file_data in hidden_tests {
+        if let Some(target) = Utf8Path::new(file_data.get_relative_path()).file_stem() {
+            arguments.extend(["--test", target]);
+        }
     }
 
-    fn get_errors(&self) -> Vec<String> {
-        self.errors.clone()
+    match run_cargo(
+        &arguments,
+        &attempt_copy.directory,
+        &attempt_copy.cargo_settings,
+        false,
+        &get_test_limits(puzzle_data, settings),
+    ) {
+        Ok(output) => {
+            // Only the names of the hidden tests are shown.
+            report.hidden_tests = parse_test_output(&output.stdout)
+                .into_iter()
+                .map(|test| TestResult {
+                    message: None,
+                    ..test
+                })
+                .collect();
+
+            if let Some(stopped) = output.stopped {
+                report.stopped = Some(stopped);
+                report.errors.push(RukataError::CargoFailure(format!(
+                    "The hidden tests were stopped because {}",
+                    stopped
+                )));
+            } else if !output.success {
+                let failed = count_status(&report.hidden_tests, TestStatus::Failed);
+                if failed > 0 {
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "The attempt has failed {} of the hidden tests",
+                        failed
+                    )));
+                } else {
+                    report.errors.push(RukataError::CargoFailure(
+                        "The hidden tests have failed to build or run".to_string(),
+                    ));
+                }
+            }
+        }
+        Err(e) => report.errors.push(e),
+    }
+}
+
+/// Runs `cargo clippy` with the lint levels set by the puzzle.
+fn run_clippy(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    stream: bool,
+    report: &mut CheckReport,
+) {
+    let clippy_lints = puzzle_data.get_clippy_lints();
+    if clippy_lints.is_empty() {
+        return;
+    }
+
+    let mut arguments = vec!["clippy", "--all-targets", "--message-format=json", "--"];
+    for (lint, level) in clippy_lints {
+        arguments.push(level.get_flag());
+        arguments.push(lint);
+    }
+
+    match run_cargo(
+        &arguments,
+        directory,
+        settings.get_cargo(),
+        stream,
+        &Limits::default(),
+    ) {
+        Ok(output) => {
+            if !output.success {
+                report.lints = parse_diagnostics(&output.stdout);
+                if report.lints.is_empty() {
+                    report.errors.push(RukataError::CargoFailure(
+                        "Run `cargo clippy` has failed".to_string(),
+                    ));
+                } else {
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "Run `cargo clippy` has failed with {} lint error(s)",
+                        report.lints.len()
+                    )));
+                }
+            }
+
+            match write_log(directory, CLIPPY_LOG_NAME, &output) {
+                Ok(log_path) => report.log_paths.push(log_path),
+                Err(e) => report.errors.push(e),
+            }
+        }
+        Err(e) => report.errors.push(e),
+    }
+}
+
+/// Runs `cargo fmt --check` when the puzzle requires formatted code.
+fn run_fmt(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    settings: &Settings,
+    stream: bool,
+    report: &mut CheckReport,
+) {
+    if !puzzle_data.get_check_formatting() {
+        return;
+    }
+
+    let arguments = ["fmt", "--check"];
+    match run_cargo(
+        &arguments,
+        directory,
+        settings.get_cargo(),
+        stream,
+        &Limits::default(),
+    ) {
+        Ok(output) => {
+            if !output.success {
+                let files = output
+                    .stdout
+                    .lines()
+                    .filter(|line| line.starts_with("Diff in "))
+                    .count();
+                if files > 0 {
+                    report.formatting = Some(output.stdout.clone());
+                    report.errors.push(RukataError::CargoFailure(format!(
+                        "Run `cargo fmt --check` has failed with {} difference(s), run `cargo fmt` to fix them",
+                        files
+                    )));
+                } else {
+                    report.errors.push(RukataError::CargoFailure(
+                        "Run `cargo fmt --check` has failed".to_string(),
+                    ));
+                }
+            }
+
+            match write_log(directory, FORMAT_LOG_NAME, &output) {
+                Ok(log_path) => report.log_paths.push(log_path),
+                Err(e) => report.errors.push(e),
+            }
+        }
+        Err(e) => report.errors.push(e),
+    }
+}
+
+fn print_diagnostics(
+    reporter: &Reporter,
+    title: &str,
+    diagnostics: &[Diagnostic],
+    puzzle_data: &PuzzleData,
+) {
+    if !diagnostics.is_empty() {
+        reporter.print_cyan_title(title);
+    }
+    for diagnostic in diagnostics {
+        reporter.print_blank_line();
+        reporter.print_red(diagnostic.get_headline());
+        if let Some(span) = diagnostic.get_primary_span() {
+            reporter.print_white(format!(
+                "  --> {}",
+                diagnostic.get_location().unwrap_or_default()
+            ));
+            if let Some(label) = &span.label {
+                reporter.print_white(format!("  {}", label));
+            }
+        }
+        for child in &diagnostic.children {
+            reporter.print_white(format!("  {}: {}", child.level, child.message));
+        }
+        if let Some(explanation) = diagnostic
+            .get_code()
+            .and_then(|code| puzzle_data.get_error_explanation(code))
+        {
+            reporter.print_green(format!("  puzzle note: {}", explanation));
+        }
+    }
+}
+
+fn print_test_results(reporter: &Reporter, title: &str, tests: &[TestResult]) {
+    if tests.is_empty() {
+        return;
+    }
+
+    reporter.print_cyan_title(title);
+    for test in tests {
+        let line = format!("{:<8}{}", test.status, test.name);
+        match test.status {
+            TestStatus::Passed => reporter.print_green(line),
+            TestStatus::Failed => reporter.print_red(line),
+            TestStatus::Ignored => reporter.print_white(line),
+        }
+    }
+    reporter.print_white(format!(
+        "{} passed, {} failed, {} ignored",
+        count_status(tests, TestStatus::Passed),
+        count_status(tests, TestStatus::Failed),
+        count_status(tests, TestStatus::Ignored)
+    ));
+}
+
+fn print_step_results(reporter: &Reporter, steps: &[StepResult]) {
+    if steps.is_empty() {
+        return;
+    }
+
+    reporter.print_cyan_title("Check steps:");
+    for step in steps {
+        let line = format!("{:<8}{}", step.status, step.step);
+        match step.status {
+            StepStatus::Passed => reporter.print_green(line),
+            StepStatus::Failed => reporter.print_red(line),
+            StepStatus::Skipped => reporter.print_white(line),
+        }
+    }
+
+    for output_diff in steps.iter().filter_map(|step| step.output_diff.as_ref()) {
+        reporter.print_blank_line();
+        for line in output_diff.lines() {
+            if line.starts_with("---") || line.starts_with("+++") {
+                reporter.print_white_bold(line);
+            } else if line.starts_with("@@") {
+                reporter.print_cyan(line);
+            } else if line.starts_with('-') {
+                reporter.print_red(line);
+            } else if line.starts_with('+') {
+                reporter.print_green(line);
+            } else {
+                reporter.print_white(line);
+            }
+        }
+    }
+}
+
+fn print_compile_fail_results(reporter: &Reporter, results: &[CompileFailResult]) {
+    if results.is_empty() {
+        return;
+    }
+
+    reporter.print_cyan_title("Compile-fail snippets:");
+    for result in results {
+        let line = format!(
+            "{:<8}{} ({})",
+            if result.passed { "ok" } else { "FAILED" },
+            result.path,
+            result.code
+        );
+        if result.passed {
+            reporter.print_green(line);
+            continue;
+        }
+
+        reporter.print_red(line);
+        if result.errors.is_empty() {
+            reporter.print_white(format!(
+                "  the snippet compiled, expected it to fail with {}",
+                result.get_expected()
+            ));
+        } else {
+            reporter.print_white(format!("  expected {}, found:", result.get_expected()));
+            for error in &result.errors {
+                reporter.print_white(format!("  {}", error.get_headline()));
+            }
+        }
+    }
+}
+
+fn print_mutant_results(reporter: &Reporter, results: &[MutantResult]) {
+    if results.is_empty() {
+        return;
+    }
+
+    reporter.print_cyan_title("Mutants:");
+    for result in results {
+        if let Some(caught_by) = &result.caught_by {
+            reporter.print_green(format!("{:<10}{} - {}", "caught", result.name, caught_by));
+        } else {
+            reporter.print_red(format!(
+                "{:<10}{} - {}",
+                "SURVIVED", result.name, result.description
+            ));
+        }
+    }
+}
+
+/// Prints the compiler errors, each test result, the output of the failing tests and the gates.
+pub(crate) fn print_check_report(
+    reporter: &Reporter,
+    report: &CheckReport,
+    puzzle_data: &PuzzleData,
+) {
+    print_diagnostics(
+        reporter,
+        "Compiler errors:",
+        &report.diagnostics,
+        puzzle_data,
+    );
+
+    print_test_results(reporter, "Test results:", &report.tests);
+    print_test_results(reporter, "Hidden test results:", &report.hidden_tests);
+
+    // Puzzles without check steps only run `cargo test`, so the step is not listed.
+    if !puzzle_data.get_check_steps().is_empty() {
+        print_step_results(reporter, &report.steps);
+    }
+
+    print_compile_fail_results(reporter, &report.compile_fail);
+    print_mutant_results(reporter, &report.mutants);
+
+    for test in &report.tests {
+        if let Some(message) = &test.message {
+            reporter.print_red(format!("\n---- {} ----", test.name));
+            reporter.print_white(message);
+        }
+    }
+
+    if let Some(stopped) = &report.stopped {
+        reporter.print_red_title(format!("The attempt was stopped because {}", stopped));
+    }
+
+    print_diagnostics(reporter, "Lint errors:", &report.lints, puzzle_data);
+
+    if let Some(formatting) = &report.formatting {
+        reporter.print_cyan_title("Formatting differences:");
+        reporter.print_styled(formatting.trim_end());
+    }
+
+    if !report.log_paths.is_empty() {
+        reporter.print_blank_line();
+    }
+    for log_path in &report.log_paths {
+        reporter.print_white(format!("Full cargo output saved to `{}`", log_path));
+    }
+}
+
+/// Adds the files of the stage after `stage` when a check passes a stage before the last.
+fn unlock_next_stage(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8Path,
+    stage: usize,
+) -> Result<Option<UnlockedStage>, Vec<RukataError>> {
+    let Some(puzzle_stage) = puzzle_data.get_stages().get(stage) else {
+        return Ok(None);
+    };
+
+    let files = populate_stage(puzzle_data, directory, stage + 1)?;
+    Ok(Some(UnlockedStage {
+        stage: stage + 2,
+        stage_count: puzzle_data.get_stage_count(),
+        name: puzzle_stage.get_name().to_string(),
+        title: puzzle_stage.get_title().to_string(),
+        description: puzzle_stage.get_description().to_string(),
+        files,
+    }))
+}
+
+/// Unlocks the next stage when the check passed a stage before the last and records the check in
+/// the progress store, adding any error to `errors`.
+///
+/// The files of an unlocked stage are taken back out when the progress store can not be saved, so
+/// the attempt stays at the stage the progress store holds.
+pub(crate) fn finish_check(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8Path,
+    stage: usize,
+    errors: &mut Vec<RukataError>,
+) -> (Option<RunTime>, Option<UnlockedStage>) {
+    let mut unlocked = None;
+    if errors.is_empty() {
+        match unlock_next_stage(puzzle_data, directory, stage) {
+            Ok(unlocked_stage) => unlocked = unlocked_stage,
+            Err(unlock_errors) => *errors = unlock_errors,
+        }
+    }
+
+    match record_check(*puzzle_data.get_id(), errors, unlocked.is_some()) {
+        Ok(run_time) => (run_time, unlocked),
+        Err(error) => {
+            errors.push(error);
+            if let Some(unlocked) = unlocked {
+                errors.extend(remove_stage_files(
+                    puzzle_data,
+                    directory,
+                    stage + 1,
+                    &unlocked.files,
+                ));
+            }
+            (None, None)
+        }
+    }
+}
+
+/// Records the result of a check in the progress store, `stage_passed` when it passed a stage
+/// before the last of a multi-stage puzzle.
+///
+/// Every check is recorded as an attempt. Returns how long the timed run took when the check
+/// finished one.
+fn record_check(
+    puzzle_id: u16,
+    errors: &[RukataError],
+    stage_passed: bool,
+) -> Result<Option<RunTime>, RukataError> {
+    let result = if errors.is_empty() {
+        CheckResult::Passed
+    } else {
+        CheckResult::Failed
+    };
+
+    let timestamp = Utc::now();
+    let mut run_time = None;
+    let error = update_progress(|progress| {
+        let puzzle_progress = progress.get_mut_puzzle(puzzle_id);
+        let previous_best_seconds = puzzle_progress
+            .get_personal_best()
+            .map(TimedRun::get_active_seconds);
+
+        // Only passing the last stage completes the puzzle and finishes the timed run.
+        if stage_passed {
+            puzzle_progress.record_stage_passed(timestamp);
+            return;
+        }
+
+        let Some(run) = puzzle_progress.record_attempt(timestamp, result) else {
+            return;
+        };
+        let active_seconds = run.get_active_seconds();
+        run_time = Some(RunTime::new(
+            active_seconds,
+            previous_best_seconds,
+            puzzle_progress.get_review().map(|review| *review.get_due()),
+        ));
+    });
+
+    match error {
+        Some(error) => Err(error),
+        None => Ok(run_time),
     }
 }
 
@@ -0,0 +1,170 @@
+use crate::argument_builder::GeneralArguments;
+use crate::command::Command;
+use crate::common::get_puzzle_folder_name;
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use rukata_puzzle_data::{get_file_data, PuzzleData};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+use similar::{ChangeTag, TextDiff};
+use std::fs;
+
+// Number of unchanged lines shown around each change.
+const CONTEXT_RADIUS: usize = 3;
+
+pub struct DiffCommand {
+    arguments: GeneralArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for DiffCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        // Get the puzzle data.
+        let puzzle_id = self.arguments.puzzle_id;
+        let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
+            Some(data) => data,
+            None => {
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
+                return;
+            }
+        };
+
+        // Get folder path.
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings.get_directory().join("working").join(folder_name);
+
+        // Check folder existing.
+        if !directory.exists() {
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` does not exist",
+                directory
+            )));
+            return;
+        }
+
+        reporter.set("puzzle_id", puzzle_id);
+        reporter.set("directory", &directory);
+
+        // Diff each file against the solution.
+        let mut files = Vec::new();
+        for file_data in puzzle_data.get_final_files() {
+            let relative_path = file_data.get_relative_path();
+            let file_path = directory.join(relative_path);
+
+            let working_data = if file_path.exists() {
+                match fs::read(&file_path) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        self.errors.push(RukataError::Io(format!(
+                            "Failed to read file `{}` with error: {}",
+                            file_path, e
+                        )));
+                        continue;
+                    }
+                }
+            } else {
+                Vec::new()
+            };
+
+            if file_data.check_data(&working_data) {
+                continue;
+            }
+
+            let working_header = format!("working/{}", relative_path);
+            let solution_header = format!("solution/{}", relative_path);
+
+            let (working_text, solution_text) = match (
+                std::str::from_utf8(&working_data),
+                std::str::from_utf8(file_data.get_raw_data()),
+            ) {
+                (Ok(working_text), Ok(solution_text)) => (working_text, solution_text),
+                _ => {
+                    reporter.print_white_bold(format!(
+                        "\nBinary files {} and {} differ",
+                        working_header, solution_header
+                    ));
+                    files.push(json!({ "path": relative_path, "binary": true, "diff": null }));
+                    continue;
+                }
+            };
+
+            let text_diff = TextDiff::from_lines(working_text, solution_text);
+            Self::print_diff(reporter, &working_header, &solution_header, &text_diff);
+
+            let unified_diff = text_diff
+                .unified_diff()
+                .context_radius(CONTEXT_RADIUS)
+                .header(&working_header, &solution_header)
+                .to_string();
+            files.push(json!({ "path": relative_path, "binary": false, "diff": unified_diff }));
+        }
+
+        let has_differences = !files.is_empty();
+        reporter.set("files", files);
+
+        if self.errors.is_empty() && !has_differences {
+            reporter.print_green(format!(
+                "Puzzle {} matches the solution, there are no differences",
+                puzzle_id
+            ));
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl DiffCommand {
+    pub fn new(arguments: GeneralArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn print_diff<'text>(
+        reporter: &Reporter,
+        working_header: &str,
+        solution_header: &str,
+        text_diff: &TextDiff<'text, 'text, '_, str>,
+    ) {
+        reporter.print_blank_line();
+        reporter.print_white_bold(format!("--- {}", working_header));
+        reporter.print_white_bold(format!("+++ {}", solution_header));
+
+        for hunk in text_diff
+            .unified_diff()
+            .context_radius(CONTEXT_RADIUS)
+            .iter_hunks()
+        {
+            reporter.print_cyan(hunk.header().to_string());
+            for change in hunk.iter_changes() {
+                let line = change.value().trim_end_matches(['\r', '\n']);
+                match change.tag() {
+                    ChangeTag::Delete => reporter.print_red(format!("-{}", line)),
+                    ChangeTag::Insert => reporter.print_green(format!("+{}", line)),
+                    ChangeTag::Equal => reporter.print_white(format!(" {}", line)),
+                }
+            }
+        }
+    }
+}
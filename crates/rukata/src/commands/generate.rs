@@ -1,7 +1,10 @@
 use crate::argument_builder::GeneralArguments;
-use crate::command::Command;
-use crate::common::generate_file;
+use crate::command::{get_progress, Command};
+use crate::common::{get_puzzle_folder_name, populate_puzzle_directory, update_progress};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
 use crate::validation::validate_settings;
+use chrono::Utc;
 use rukata_puzzle_data::{get_file_data, PuzzleData};
 use rukata_settings::SettingsHandler;
 use std::fs;
@@ -9,7 +12,7 @@ use std::fs;
 pub struct GenerateCommand {
     arguments: GeneralArguments,
     settings: Option<SettingsHandler>,
-    errors: Vec<String>,
+    errors: Vec<RukataError>,
 }
 
 impl Command for GenerateCommand {
@@ -19,7 +22,7 @@ impl Command for GenerateCommand {
 
     fn initialize(&mut self) {}
 
-    fn execute(&mut self) {
+    fn execute(&mut self, reporter: &mut Reporter) {
         let settings_handler = self.settings.as_ref().expect("Failed to set settings");
 
         // Get the settings
@@ -34,28 +37,31 @@ impl Command for GenerateCommand {
         let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
             Some(data) => data,
             None => {
-                self.errors
-                    .push(format!("Puzzle ID is not valid {}", puzzle_id));
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
                 return;
             }
         };
 
+        reporter.set("puzzle_id", puzzle_id);
+
         // Generate the main folder.
-        let title = puzzle_data.get_title();
-        let folder_name = format!("p{:0>5} - {}", puzzle_id, title);
+        let folder_name = get_puzzle_folder_name(puzzle_data);
 
         let directory = settings.get_directory().join("working").join(folder_name);
+        reporter.set("directory", &directory);
         if directory.exists() {
-            self.errors
-                .push(format!("Directory `{}` already exists", directory));
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` already exists",
+                directory
+            )));
         }
 
         if !directory.exists() {
             if let Err(e) = fs::create_dir_all(directory.clone()) {
-                self.errors.push(format!(
+                self.errors.push(RukataError::Io(format!(
                     "Failed to create directory `{}` with error: {}",
                     directory, e
-                ));
+                )));
             }
         }
 
@@ -64,43 +70,44 @@ impl Command for GenerateCommand {
         }
 
         // Populate the main folder.
-        for file_data in puzzle_data.get_base_files() {
-            let file_path = directory.join(file_data.get_relative_path());
-            if let Some(error) = generate_file(file_path, file_data.get_raw_data()) {
-                self.errors.push(error);
-            }
-        }
-
+        self.errors = populate_puzzle_directory(puzzle_data, &directory);
         if !self.errors.is_empty() {
             return;
         }
 
-        // Mark files read-only.
-        let read_only_files = puzzle_data.get_read_only_file_paths();
-        for file_name in read_only_files {
-            let file_path = directory.join(file_name);
-            match file_path.metadata() {
-                Ok(metadata) => {
-                    let mut permissions = metadata.permissions();
-                    permissions.set_readonly(true);
-                    if let Err(e) = fs::set_permissions(&file_path, permissions) {
-                        self.errors.push(format!(
-                            "Failed to modify metadata for `{}` with error: {}",
-                            file_path, e
-                        ));
-                    }
-                }
-                Err(e) => {
-                    self.errors.push(format!(
-                        "Failed to read metadata for `{}` with error: {}",
-                        file_path, e
-                    ));
-                }
+        // Point out any prerequisites that still have to be completed.
+        let unmet_requires: Vec<u16> = match get_progress() {
+            Ok(progress_handler) => puzzle_data
+                .get_requires()
+                .iter()
+                .copied()
+                .filter(|required_id| !progress_handler.get_progress().is_completed(*required_id))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        reporter.set("unmet_requires", &unmet_requires);
+        for required_id in unmet_requires {
+            if let Some(required_data) = get_file_data(required_id) {
+                reporter.print_red(format!(
+                    "Puzzle {} builds on puzzle {} - {}, which has not been completed yet",
+                    puzzle_id,
+                    required_id,
+                    required_data.get_title()
+                ));
             }
         }
+
+        // Record the generation.
+        if let Some(error) = update_progress(|progress| {
+            progress
+                .get_mut_puzzle(puzzle_id)
+                .record_generated(Utc::now())
+        }) {
+            self.errors.push(error);
+        }
     }
 
-    fn get_errors(&self) -> Vec<String> {
+    fn get_errors(&self) -> Vec<RukataError> {
         self.errors.clone()
     }
 }
@@ -0,0 +1,101 @@
+use crate::argument_builder::GeneralArguments;
+use crate::command::Command;
+use crate::common::update_progress;
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use chrono::Utc;
+use rukata_puzzle_data::{get_file_data, PuzzleData};
+use rukata_settings::SettingsHandler;
+
+pub struct HintCommand {
+    arguments: GeneralArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for HintCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        // Get the puzzle data.
+        let puzzle_id = self.arguments.puzzle_id;
+        let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
+            Some(data) => data,
+            None => {
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
+                return;
+            }
+        };
+
+        reporter.set("puzzle_id", puzzle_id);
+
+        let hints = puzzle_data.get_hints();
+        reporter.set("total", hints.len());
+        if hints.is_empty() {
+            reporter.set("revealed", Vec::<&str>::new());
+            reporter.print_white(format!("Puzzle {} does not have any hints", puzzle_id));
+            return;
+        }
+
+        // Reveal the next hint.
+        let mut generated = true;
+        let mut previously_revealed = 0;
+        let mut revealed = 0;
+        if let Some(error) = update_progress(|progress| {
+            let Some(puzzle_progress) = progress.get_mut_generated_puzzle(puzzle_id) else {
+                generated = false;
+                return;
+            };
+            previously_revealed = puzzle_progress.get_hints_revealed();
+            revealed = puzzle_progress.reveal_hint(hints.len());
+            puzzle_progress.record_activity(Utc::now());
+        }) {
+            self.errors.push(error);
+            return;
+        }
+
+        if !generated {
+            self.errors.push(RukataError::Io(format!(
+                "Puzzle {} has not been generated, run `rukata generate {}` first",
+                puzzle_id, puzzle_id
+            )));
+            return;
+        }
+
+        reporter.set("revealed", &hints[..revealed]);
+        reporter.set("new_hint", revealed != previously_revealed);
+
+        reporter.print_cyan_title(format!("Hints for puzzle {}:", puzzle_id));
+        for (index, hint) in hints.iter().take(revealed).enumerate() {
+            let line = format!("{}/{}. {}", index + 1, hints.len(), hint);
+            if index >= previously_revealed {
+                reporter.print_green(line);
+            } else {
+                reporter.print_white(line);
+            }
+        }
+
+        if revealed == previously_revealed {
+            reporter
+                .print_white("All of the hints have been revealed, try `rukata solution` next.");
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl HintCommand {
+    pub fn new(arguments: GeneralArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+}
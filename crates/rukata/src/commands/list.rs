@@ -0,0 +1,194 @@
+use crate::argument_builder::{ListArguments, SortArgument};
+use crate::command::{get_progress, Command};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use rukata_progress::versions::v1::{Progress, PuzzleState};
+use rukata_puzzle_data::{
+    get_file_data, get_id_list, get_ordered_id_list, PuzzleData, PuzzleDifficulty,
+};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+const TABLE_HEADERS: &[&str; 7] = &[
+    "ID",
+    "Title",
+    "Difficulty",
+    "Categories",
+    "Libraries",
+    "Requires",
+    "State",
+];
+
+pub struct ListCommand {
+    arguments: ListArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for ListCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        // Progress is only required when filtering by state.
+        let progress = match get_progress() {
+            Ok(handler) => Some(handler.get_progress().clone()),
+            Err(e) => {
+                if self.arguments.state.is_some() {
+                    self.errors.push(e);
+                    return;
+                }
+                None
+            }
+        };
+
+        let puzzles = self.get_puzzles(progress.as_ref());
+
+        Self::print_human(reporter, &puzzles);
+        reporter.set("puzzles", Self::to_json(&puzzles));
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl ListCommand {
+    pub fn new(arguments: ListArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn get_puzzles(
+        &self,
+        progress: Option<&Progress>,
+    ) -> Vec<(&'static PuzzleData, Option<PuzzleState>)> {
+        let difficulty = self.arguments.difficulty.map(PuzzleDifficulty::from);
+        let state = self.arguments.state.map(PuzzleState::from);
+
+        let mut puzzles: Vec<(&'static PuzzleData, Option<PuzzleState>)> = get_id_list()
+            .into_iter()
+            .filter_map(|puzzle_id| get_file_data(*puzzle_id))
+            .map(|puzzle_data| {
+                let puzzle_state =
+                    progress.map(|progress| progress.get_state(*puzzle_data.get_id()));
+                (puzzle_data, puzzle_state)
+            })
+            .filter(|(puzzle_data, puzzle_state)| {
+                if let Some(difficulty) = &difficulty {
+                    if puzzle_data.get_difficulty() != difficulty {
+                        return false;
+                    }
+                }
+
+                if let Some(category) = &self.arguments.category {
+                    if !Self::contains_ignore_case(puzzle_data.get_categories(), category) {
+                        return false;
+                    }
+                }
+
+                if let Some(library) = &self.arguments.library {
+                    if !Self::contains_ignore_case(puzzle_data.get_libraries(), library) {
+                        return false;
+                    }
+                }
+
+                if let Some(state) = &state {
+                    if puzzle_state.as_ref() != Some(state) {
+                        return false;
+                    }
+                }
+
+                true
+            })
+            .collect();
+
+        match self.arguments.sort {
+            SortArgument::Planned => {
+                let ordered_id_list = get_ordered_id_list();
+                puzzles.sort_by_key(|(puzzle_data, _)| {
+                    ordered_id_list
+                        .iter()
+                        .position(|id| *id == puzzle_data.get_id())
+                })
+            }
+            SortArgument::Id => puzzles.sort_by_key(|(puzzle_data, _)| *puzzle_data.get_id()),
+            SortArgument::Title => puzzles.sort_by(|(left, _), (right, _)| {
+                left.get_title()
+                    .to_lowercase()
+                    .cmp(&right.get_title().to_lowercase())
+                    .then(left.get_id().cmp(right.get_id()))
+            }),
+            SortArgument::Difficulty => puzzles.sort_by(|(left, _), (right, _)| {
+                left.get_difficulty()
+                    .cmp(right.get_difficulty())
+                    .then(left.get_id().cmp(right.get_id()))
+            }),
+        }
+
+        if self.arguments.reverse {
+            puzzles.reverse();
+        }
+
+        puzzles
+    }
+
+    fn contains_ignore_case(values: &[&str], value: &str) -> bool {
+        values.iter().any(|item| item.eq_ignore_ascii_case(value))
+    }
+
+    fn print_human(reporter: &Reporter, puzzles: &[(&'static PuzzleData, Option<PuzzleState>)]) {
+        if puzzles.is_empty() {
+            reporter.print_white("No puzzles match the given filters.");
+            return;
+        }
+
+        let rows: Vec<Vec<String>> = puzzles
+            .iter()
+            .map(|(puzzle_data, puzzle_state)| {
+                vec![
+                    format!("p{:0>5}", puzzle_data.get_id()),
+                    puzzle_data.get_title().to_string(),
+                    puzzle_data.get_difficulty().to_string(),
+                    puzzle_data.get_categories().join(", "),
+                    puzzle_data.get_libraries().join(", "),
+                    puzzle_data
+                        .get_requires()
+                        .iter()
+                        .map(|id| format!("p{:0>5}", id))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    match puzzle_state {
+                        Some(puzzle_state) => puzzle_state.to_string(),
+                        None => "-".to_string(),
+                    },
+                ]
+            })
+            .collect();
+
+        reporter.print_table(TABLE_HEADERS, &rows);
+    }
+
+    fn to_json(puzzles: &[(&'static PuzzleData, Option<PuzzleState>)]) -> Vec<serde_json::Value> {
+        puzzles
+            .iter()
+            .map(|(puzzle_data, puzzle_state)| {
+                json!({
+                    "id": puzzle_data.get_id(),
+                    "title": puzzle_data.get_title(),
+                    "difficulty": puzzle_data.get_difficulty().to_string(),
+                    "categories": puzzle_data.get_categories(),
+                    "libraries": puzzle_data.get_libraries(),
+                    "requires": puzzle_data.get_requires(),
+                    "state": puzzle_state,
+                })
+            })
+            .collect()
+    }
+}
@@ -1,4 +1,15 @@
 pub mod check;
+pub mod diff;
 pub mod generate;
+pub mod hint;
+pub mod list;
+pub mod next;
+pub mod progress;
+pub mod redo;
+pub mod reset;
+pub mod review;
+pub mod search;
 pub mod settings;
 pub mod solution;
+pub mod track;
+pub mod watch;
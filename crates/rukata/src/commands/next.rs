@@ -0,0 +1,520 @@
+use crate::argument_builder::NextArguments;
+use crate::command::{get_progress, Command};
+use crate::common::{format_timestamp, get_puzzle_folder_name, start_new_attempt};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use chrono::{DateTime, Utc};
+use rukata_progress::versions::v1::{Progress, PuzzleState};
+use rukata_puzzle_data::{get_file_data, get_id_list, PuzzleData, PuzzleDifficulty};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+
+/// A puzzle picked for the user along with why it was picked.
+struct Recommendation<'a, P> {
+    puzzle_data: &'a P,
+    reasons: Vec<String>,
+    review: bool,
+}
+
+/// What a recommendation is picked by, taken from the puzzle data.
+trait Recommendable {
+    fn get_id(&self) -> &u16;
+    fn get_difficulty(&self) -> &PuzzleDifficulty;
+    fn get_categories(&self) -> &[&str];
+    fn get_libraries(&self) -> &[&str];
+    fn get_requires(&self) -> &[u16];
+}
+
+impl Recommendable for PuzzleData {
+    fn get_id(&self) -> &u16 {
+        PuzzleData::get_id(self)
+    }
+
+    fn get_difficulty(&self) -> &PuzzleDifficulty {
+        PuzzleData::get_difficulty(self)
+    }
+
+    fn get_categories(&self) -> &[&str] {
+        PuzzleData::get_categories(self)
+    }
+
+    fn get_libraries(&self) -> &[&str] {
+        PuzzleData::get_libraries(self)
+    }
+
+    fn get_requires(&self) -> &[u16] {
+        PuzzleData::get_requires(self)
+    }
+}
+
+pub struct NextCommand {
+    arguments: NextArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for NextCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        let progress_handler = match get_progress() {
+            Ok(handler) => handler,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+        let progress = progress_handler.get_progress();
+
+        let puzzles: Vec<&'static PuzzleData> = get_id_list()
+            .into_iter()
+            .filter_map(|puzzle_id| get_file_data(*puzzle_id))
+            .collect();
+        let Some(recommendation) = Self::recommend(progress, &puzzles, Utc::now()) else {
+            reporter.set("recommendation", serde_json::Value::Null);
+            reporter.print_green("You have completed every puzzle and none are due for review.");
+            if let Some(due) = progress
+                .get_puzzles()
+                .values()
+                .filter_map(|puzzle_progress| puzzle_progress.get_review())
+                .map(|review| review.get_due())
+                .min()
+            {
+                reporter.print_white(format!(
+                    "The next review is due on {}",
+                    format_timestamp(Some(due))
+                ));
+            }
+            return;
+        };
+
+        let puzzle_data = recommendation.puzzle_data;
+        let puzzle_id = *puzzle_data.get_id();
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings
+            .get_directory()
+            .join("working")
+            .join(folder_name.clone());
+        let attempts_directory = settings.get_directory().join("attempts").join(folder_name);
+
+        reporter.set(
+            "recommendation",
+            json!({
+                "id": puzzle_id,
+                "title": puzzle_data.get_title(),
+                "difficulty": puzzle_data.get_difficulty().to_string(),
+                "categories": puzzle_data.get_categories(),
+                "libraries": puzzle_data.get_libraries(),
+                "review": recommendation.review,
+                "reasons": recommendation.reasons,
+            }),
+        );
+        reporter.set("directory", &directory);
+
+        reporter.print_cyan_title(format!(
+            "Next puzzle: p{:0>5} - {} ({})",
+            puzzle_id,
+            puzzle_data.get_title(),
+            puzzle_data.get_difficulty()
+        ));
+        for reason in &recommendation.reasons {
+            reporter.print_white(format!("- {}", reason));
+        }
+        reporter.print_blank_line();
+
+        // A puzzle being worked on is left as it is, a review starts again from the starter files.
+        let mut generated = false;
+        if directory.exists() && !recommendation.review {
+            reporter.print_white(format!("Carry on with it in `{}`", directory));
+        } else if self.arguments.generate
+            || reporter.confirm(format!("Generate puzzle {} now?", puzzle_id))
+        {
+            match start_new_attempt(puzzle_data, &directory, &attempts_directory) {
+                Ok(archived) => {
+                    if let Some(archived) = archived {
+                        reporter.print_white(format!(
+                            "Archived the current attempt as attempt {} in `{}`",
+                            archived.number, archived.directory
+                        ));
+                    }
+                    reporter
+                        .print_green(format!("Generated puzzle {} in `{}`", puzzle_id, directory));
+                    generated = true;
+                }
+                Err(errors) => self.errors.extend(errors),
+            }
+        } else {
+            reporter.print_white(format!(
+                "Run `rukata generate {}` when you are ready to start",
+                puzzle_id
+            ));
+        }
+
+        reporter.set("generated", generated);
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl NextCommand {
+    pub fn new(arguments: NextArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    /// Picks the next puzzle, preferring one already started, then the easiest difficulty with
+    /// puzzles left, then the puzzle covering the most categories and libraries not yet tried.
+    /// Puzzles not started yet are only picked once the puzzles they require are completed.
+    ///
+    /// Once every puzzle is completed the puzzle in `puzzles` with the longest overdue review at
+    /// `timestamp` is picked instead.
+    fn recommend<'a, P: Recommendable>(
+        progress: &Progress,
+        puzzles: &[&'a P],
+        timestamp: DateTime<Utc>,
+    ) -> Option<Recommendation<'a, P>> {
+        // Topics from every puzzle the user has generated count as tried.
+        let mut tried_categories = BTreeSet::new();
+        let mut tried_libraries = BTreeSet::new();
+        for puzzle_data in puzzles {
+            if progress.get_state(*puzzle_data.get_id()) != PuzzleState::NotStarted {
+                tried_categories.extend(puzzle_data.get_categories().iter().copied());
+                tried_libraries.extend(puzzle_data.get_libraries().iter().copied());
+            }
+        }
+
+        let get_new_topics = |puzzle_data: &'a P| {
+            let categories: Vec<&str> = puzzle_data
+                .get_categories()
+                .iter()
+                .copied()
+                .filter(|category| !tried_categories.contains(category))
+                .collect();
+            let libraries: Vec<&str> = puzzle_data
+                .get_libraries()
+                .iter()
+                .copied()
+                .filter(|library| !tried_libraries.contains(library))
+                .collect();
+            (categories, libraries)
+        };
+
+        let Some(puzzle_data) = puzzles
+            .iter()
+            .copied()
+            .filter(|puzzle_data| !progress.is_completed(*puzzle_data.get_id()))
+            .filter(|puzzle_data| {
+                progress.get_state(*puzzle_data.get_id()) == PuzzleState::InProgress
+                    || Self::has_completed_requires(progress, *puzzle_data)
+            })
+            .min_by_key(|puzzle_data| {
+                let (categories, libraries) = get_new_topics(puzzle_data);
+                (
+                    progress.get_state(*puzzle_data.get_id()) != PuzzleState::InProgress,
+                    puzzle_data.get_difficulty().clone(),
+                    Reverse(categories.len() + libraries.len()),
+                    *puzzle_data.get_id(),
+                )
+            })
+        else {
+            return Self::recommend_review(progress, puzzles, timestamp);
+        };
+
+        let mut reasons = Vec::new();
+        let in_progress = progress.get_state(*puzzle_data.get_id()) == PuzzleState::InProgress;
+        if in_progress {
+            reasons.push("You have started it but no check has passed yet".to_string());
+        }
+
+        if !puzzle_data.get_requires().is_empty()
+            && Self::has_completed_requires(progress, puzzle_data)
+        {
+            reasons.push(format!(
+                "You have completed the puzzles it builds on, {}",
+                puzzle_data
+                    .get_requires()
+                    .iter()
+                    .map(|id| format!("p{:0>5}", id))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+
+        // Explain where the puzzle sits on the difficulty ladder.
+        let difficulty = puzzle_data.get_difficulty();
+        let level: Vec<&&P> = puzzles
+            .iter()
+            .filter(|other| other.get_difficulty() == difficulty)
+            .collect();
+        let completed = level
+            .iter()
+            .filter(|other| progress.is_completed(*other.get_id()))
+            .count();
+        let easiest_left = puzzles
+            .iter()
+            .filter(|other| !progress.is_completed(*other.get_id()))
+            .all(|other| other.get_difficulty() >= difficulty);
+        if easiest_left {
+            reasons.push(format!(
+                "{} is the easiest difficulty you have puzzles left in, {} of {} are completed",
+                difficulty,
+                completed,
+                level.len()
+            ));
+        }
+
+        // The topics of a puzzle already started have been tried by starting it.
+        if !in_progress {
+            let (new_categories, new_libraries) = get_new_topics(puzzle_data);
+            if !new_categories.is_empty() {
+                reasons.push(format!(
+                    "It covers {}, which you have not tried yet",
+                    new_categories.join(", ")
+                ));
+            } else if !puzzle_data.get_categories().is_empty() {
+                reasons.push(format!(
+                    "It builds on {}, which you have practised before",
+                    puzzle_data.get_categories().join(", ")
+                ));
+            }
+
+            if !new_libraries.is_empty() {
+                reasons.push(format!(
+                    "It uses {}, which you have not used yet",
+                    new_libraries.join(", ")
+                ));
+            }
+        }
+
+        Some(Recommendation {
+            puzzle_data,
+            reasons,
+            review: false,
+        })
+    }
+
+    fn has_completed_requires<P: Recommendable>(progress: &Progress, puzzle_data: &P) -> bool {
+        puzzle_data
+            .get_requires()
+            .iter()
+            .all(|required_id| progress.is_completed(*required_id))
+    }
+
+    fn recommend_review<'a, P: Recommendable>(
+        progress: &Progress,
+        puzzles: &[&'a P],
+        timestamp: DateTime<Utc>,
+    ) -> Option<Recommendation<'a, P>> {
+        let puzzle_data =
+            progress
+                .get_due_reviews(timestamp)
+                .into_iter()
+                .find_map(|puzzle_id| {
+                    puzzles
+                        .iter()
+                        .copied()
+                        .find(|puzzle_data| *puzzle_data.get_id() == puzzle_id)
+                })?;
+        let review = progress.get_puzzle(*puzzle_data.get_id())?.get_review()?;
+
+        Some(Recommendation {
+            puzzle_data,
+            reasons: vec![
+                "You have completed every puzzle".to_string(),
+                format!(
+                    "It has been due for review since {}",
+                    format_timestamp(Some(review.get_due()))
+                ),
+            ],
+            review: true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rukata_progress::versions::v1::CheckResult;
+
+    struct TestPuzzle {
+        id: u16,
+        difficulty: PuzzleDifficulty,
+        categories: Vec<&'static str>,
+        requires: Vec<u16>,
+    }
+
+    impl Recommendable for TestPuzzle {
+        fn get_id(&self) -> &u16 {
+            &self.id
+        }
+
+        fn get_difficulty(&self) -> &PuzzleDifficulty {
+            &self.difficulty
+        }
+
+        fn get_categories(&self) -> &[&str] {
+            &self.categories
+        }
+
+        fn get_libraries(&self) -> &[&str] {
+            &[]
+        }
+
+        fn get_requires(&self) -> &[u16] {
+            &self.requires
+        }
+    }
+
+    fn puzzle(
+        id: u16,
+        difficulty: PuzzleDifficulty,
+        categories: &[&'static str],
+        requires: &[u16],
+    ) -> TestPuzzle {
+        TestPuzzle {
+            id,
+            difficulty,
+            categories: categories.to_vec(),
+            requires: requires.to_vec(),
+        }
+    }
+
+    fn timestamp(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, day, 10, 0, 0).unwrap()
+    }
+
+    fn complete(progress: &mut Progress, puzzle_id: u16, day: u32) {
+        let puzzle_progress = progress.get_mut_puzzle(puzzle_id);
+        puzzle_progress.record_generated(timestamp(day));
+        puzzle_progress.record_attempt(timestamp(day), CheckResult::Passed);
+    }
+
+    fn recommend_id(progress: &Progress, puzzles: &[TestPuzzle], day: u32) -> Option<u16> {
+        let puzzles: Vec<&TestPuzzle> = puzzles.iter().collect();
+        NextCommand::recommend(progress, &puzzles, timestamp(day))
+            .map(|recommendation| *recommendation.puzzle_data.get_id())
+    }
+
+    #[test]
+    fn test_recommend_prerequisites() {
+        let puzzles = [
+            puzzle(1, PuzzleDifficulty::Basic, &["Basics"], &[]),
+            puzzle(2, PuzzleDifficulty::Basic, &["Strings"], &[1]),
+        ];
+        let mut progress = Progress::default();
+        assert_eq!(recommend_id(&progress, &puzzles, 1), Some(1));
+        assert_eq!(recommend_id(&progress, &puzzles[1..], 1), None);
+
+        complete(&mut progress, 1, 1);
+        assert_eq!(recommend_id(&progress, &puzzles, 1), Some(2));
+    }
+
+    #[test]
+    fn test_recommend_in_progress_first() {
+        let puzzles = [
+            puzzle(1, PuzzleDifficulty::Basic, &["Basics"], &[]),
+            puzzle(2, PuzzleDifficulty::Advanced, &["Basics"], &[]),
+        ];
+        let mut progress = Progress::default();
+        progress.get_mut_puzzle(2).record_generated(timestamp(1));
+
+        let puzzles: Vec<&TestPuzzle> = puzzles.iter().collect();
+        let recommendation = NextCommand::recommend(&progress, &puzzles, timestamp(1)).unwrap();
+        assert_eq!(*recommendation.puzzle_data.get_id(), 2);
+        assert!(!recommendation.review);
+        assert_eq!(
+            recommendation.reasons[0],
+            "You have started it but no check has passed yet"
+        );
+    }
+
+    #[test]
+    fn test_recommend_easiest_difficulty() {
+        // The harder puzzle covers more new topics and has a lower ID.
+        let puzzles = [
+            puzzle(
+                1,
+                PuzzleDifficulty::Intermediate,
+                &["Traits", "Generics"],
+                &[],
+            ),
+            puzzle(2, PuzzleDifficulty::Basic, &["Strings"], &[]),
+        ];
+        assert_eq!(recommend_id(&Progress::default(), &puzzles, 1), Some(2));
+    }
+
+    #[test]
+    fn test_recommend_new_topics() {
+        let puzzles = [
+            puzzle(1, PuzzleDifficulty::Basic, &["Basics"], &[]),
+            puzzle(2, PuzzleDifficulty::Basic, &["Basics", "Strings"], &[]),
+            puzzle(3, PuzzleDifficulty::Basic, &["Strings", "Slices"], &[]),
+        ];
+        let mut progress = Progress::default();
+        assert_eq!(recommend_id(&progress, &puzzles, 1), Some(2));
+
+        // Once the basics are tried puzzle 3 has more to teach.
+        complete(&mut progress, 1, 1);
+        let puzzles: Vec<&TestPuzzle> = puzzles.iter().collect();
+        let recommendation = NextCommand::recommend(&progress, &puzzles, timestamp(1)).unwrap();
+        assert_eq!(*recommendation.puzzle_data.get_id(), 3);
+        assert!(recommendation
+            .reasons
+            .contains(&"It covers Strings, Slices, which you have not tried yet".to_string()));
+    }
+
+    #[test]
+    fn test_recommend_lowest_id() {
+        let puzzles = [
+            puzzle(2, PuzzleDifficulty::Basic, &["Strings"], &[]),
+            puzzle(1, PuzzleDifficulty::Basic, &["Basics"], &[]),
+        ];
+        assert_eq!(recommend_id(&Progress::default(), &puzzles, 1), Some(1));
+    }
+
+    #[test]
+    fn test_recommend_review() {
+        let puzzles = [
+            puzzle(1, PuzzleDifficulty::Basic, &["Basics"], &[]),
+            puzzle(2, PuzzleDifficulty::Basic, &["Strings"], &[]),
+            puzzle(3, PuzzleDifficulty::Basic, &["Slices"], &[]),
+        ];
+        let mut progress = Progress::default();
+        complete(&mut progress, 2, 1);
+        complete(&mut progress, 1, 2);
+        complete(&mut progress, 3, 3);
+
+        // Nothing is left to do until the first review is due.
+        assert_eq!(recommend_id(&progress, &puzzles, 1), None);
+
+        // The longest overdue review comes first, out of the puzzles given.
+        let puzzles_list: Vec<&TestPuzzle> = puzzles.iter().collect();
+        let recommendation =
+            NextCommand::recommend(&progress, &puzzles_list, timestamp(20)).unwrap();
+        assert_eq!(*recommendation.puzzle_data.get_id(), 2);
+        assert!(recommendation.review);
+        assert_eq!(recommend_id(&progress, &puzzles[2..], 20), Some(3));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::command::{get_progress, Command};
+use crate::common::{format_duration, format_timestamp};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use rukata_progress::ProgressHandler;
+use rukata_puzzle_data::{get_file_data, get_id_list, PuzzleDifficulty};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+const DIFFICULTIES: &[PuzzleDifficulty; 4] = &[
+    PuzzleDifficulty::Basic,
+    PuzzleDifficulty::Intermediate,
+    PuzzleDifficulty::Advanced,
+    PuzzleDifficulty::None,
+];
+
+const TABLE_HEADERS: &[&str; 9] = &[
+    "ID",
+    "Title",
+    "Generated",
+    "Attempts",
+    "Hints",
+    "Stage",
+    "First pass",
+    "Latest pass",
+    "Best time (estimated)",
+];
+
+#[derive(Default)]
+pub struct ProgressCommand {
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for ProgressCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let progress_handler = match get_progress() {
+            Ok(handler) => handler,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+
+        Self::print_tables(reporter, &progress_handler);
+        reporter.set("puzzles", Self::to_json(&progress_handler));
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl ProgressCommand {
+    pub fn new() -> Self {
+        Self {
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn print_tables(reporter: &Reporter, progress_handler: &ProgressHandler) {
+        let progress = progress_handler.get_progress();
+        if progress.get_puzzles().is_empty() {
+            reporter.print_white("No progress has been recorded yet.");
+            return;
+        }
+
+        for difficulty in DIFFICULTIES {
+            let mut rows = Vec::new();
+            for puzzle_id in get_id_list() {
+                let puzzle_data = get_file_data(*puzzle_id).expect("Puzzle ID from list is valid");
+                if puzzle_data.get_difficulty() != difficulty {
+                    continue;
+                }
+
+                if let Some(puzzle_progress) = progress.get_puzzle(*puzzle_id) {
+                    rows.push(vec![
+                        format!("p{:0>5}", puzzle_id),
+                        puzzle_data.get_title().to_string(),
+                        format_timestamp(puzzle_progress.get_generated()),
+                        puzzle_progress.get_attempts().len().to_string(),
+                        format!(
+                            "{}/{}",
+                            puzzle_progress.get_hints_revealed(),
+                            puzzle_data.get_hints().len()
+                        ),
+                        match puzzle_data.get_stage_count() {
+                            1 => "-".to_string(),
+                            stage_count => format!(
+                                "{}/{}",
+                                puzzle_progress.get_stage().min(stage_count - 1) + 1,
+                                stage_count
+                            ),
+                        },
+                        format_timestamp(puzzle_progress.get_first_pass()),
+                        format_timestamp(puzzle_progress.get_latest_pass()),
+                        puzzle_progress
+                            .get_personal_best()
+                            .map(|run| format_duration(run.get_active_seconds()))
+                            .unwrap_or_else(|| "-".to_string()),
+                    ]);
+                }
+            }
+
+            if rows.is_empty() {
+                continue;
+            }
+
+            reporter.print_cyan_title(format!("{}:", difficulty));
+            reporter.print_table(TABLE_HEADERS, &rows);
+        }
+    }
+
+    fn to_json(progress_handler: &ProgressHandler) -> Vec<serde_json::Value> {
+        let progress = progress_handler.get_progress();
+
+        let mut puzzles = Vec::new();
+        for (puzzle_id, puzzle_progress) in progress.get_puzzles() {
+            let (title, difficulty) = match get_file_data(*puzzle_id) {
+                Some(puzzle_data) => (
+                    Some(puzzle_data.get_title()),
+                    Some(puzzle_data.get_difficulty().to_string()),
+                ),
+                None => (None, None),
+            };
+
+            puzzles.push(json!({
+                "id": puzzle_id,
+                "title": title,
+                "difficulty": difficulty,
+                "progress": puzzle_progress,
+            }));
+        }
+
+        puzzles
+    }
+}
@@ -0,0 +1,178 @@
+use crate::argument_builder::RedoArguments;
+use crate::cargo::RUKATA_FOLDER;
+use crate::command::Command;
+use crate::common::{
+    format_timestamp, get_attempt_name, get_attempt_numbers, get_modified_times,
+    get_puzzle_folder_name, start_new_attempt,
+};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use camino::Utf8Path;
+use rukata_puzzle_data::{get_file_data, PuzzleData};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+const TABLE_HEADERS: &[&str; 3] = &["Attempt", "Last changed", "Directory"];
+
+pub struct RedoCommand {
+    arguments: RedoArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for RedoCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        // Get the puzzle data.
+        let puzzle_id = self.arguments.puzzle_id;
+        let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
+            Some(data) => data,
+            None => {
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
+                return;
+            }
+        };
+
+        // Get folder paths.
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings
+            .get_directory()
+            .join("working")
+            .join(folder_name.clone());
+        let attempts_directory = settings.get_directory().join("attempts").join(folder_name);
+
+        reporter.set("puzzle_id", puzzle_id);
+        reporter.set("directory", &directory);
+
+        let attempts = match get_attempt_numbers(&attempts_directory) {
+            Ok(attempts) => attempts,
+            Err(error) => {
+                self.errors.push(error);
+                return;
+            }
+        };
+
+        if self.arguments.list {
+            self.list_attempts(reporter, &attempts_directory, &attempts);
+            return;
+        }
+
+        if let Some(number) = self.arguments.open {
+            self.open_attempt(reporter, &attempts_directory, &attempts, number);
+            return;
+        }
+
+        match start_new_attempt(puzzle_data, &directory, &attempts_directory) {
+            Ok(Some(archived)) => {
+                reporter.set("archived", &archived.directory);
+                reporter.print_white(format!(
+                    "Archived the current attempt as attempt {} in `{}`",
+                    archived.number, archived.directory
+                ));
+            }
+            Ok(None) => {}
+            Err(errors) => {
+                self.errors = errors;
+                return;
+            }
+        }
+
+        reporter.print_green(format!(
+            "Generated a fresh start at puzzle {} in `{}`",
+            puzzle_id, directory
+        ));
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl RedoCommand {
+    pub fn new(arguments: RedoArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn list_attempts(
+        &mut self,
+        reporter: &mut Reporter,
+        attempts_directory: &Utf8Path,
+        attempts: &[usize],
+    ) {
+        let mut rows = Vec::new();
+        let mut attempts_json = Vec::new();
+        for number in attempts {
+            let attempt_directory = attempts_directory.join(get_attempt_name(*number));
+            let last_changed = get_modified_times(&attempt_directory, &["target", RUKATA_FOLDER])
+                .into_iter()
+                .max();
+
+            rows.push(vec![
+                number.to_string(),
+                format_timestamp(last_changed.as_ref()),
+                attempt_directory.to_string(),
+            ]);
+            attempts_json.push(json!({
+                "attempt": number,
+                "last_changed": last_changed,
+                "directory": attempt_directory,
+            }));
+        }
+
+        reporter.set("attempts", attempts_json);
+
+        if rows.is_empty() {
+            reporter.print_white(format!(
+                "No archived attempts found for puzzle {}",
+                self.arguments.puzzle_id
+            ));
+            return;
+        }
+
+        reporter.print_cyan_title(format!("Attempts in `{}`:", attempts_directory));
+        reporter.print_table(TABLE_HEADERS, &rows);
+    }
+
+    fn open_attempt(
+        &mut self,
+        reporter: &mut Reporter,
+        attempts_directory: &Utf8Path,
+        attempts: &[usize],
+        number: usize,
+    ) {
+        let attempt_directory = attempts_directory.join(get_attempt_name(number));
+        if !attempts.contains(&number) {
+            self.errors.push(RukataError::Io(format!(
+                "Attempt `{}` does not exist",
+                attempt_directory
+            )));
+            return;
+        }
+
+        reporter.set("attempt", number);
+        reporter.set("attempt_directory", &attempt_directory);
+        reporter.print_white(format!(
+            "Attempt {} at puzzle {} is in `{}`",
+            number, self.arguments.puzzle_id, attempt_directory
+        ));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::argument_builder::ResetArguments;
+use crate::command::Command;
+use crate::common::{
+    get_current_stage, get_puzzle_folder_name, populate_puzzle_directory, read_stage_marker,
+    update_progress, write_stage_marker,
+};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use chrono::Local;
+use rukata_puzzle_data::{get_file_data, PuzzleData};
+use rukata_settings::SettingsHandler;
+use std::fs;
+
+pub struct ResetCommand {
+    arguments: ResetArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for ResetCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        // Get the puzzle data.
+        let puzzle_id = self.arguments.puzzle_id;
+        let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
+            Some(data) => data,
+            None => {
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
+                return;
+            }
+        };
+
+        // Get folder paths.
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings
+            .get_directory()
+            .join("working")
+            .join(folder_name.clone());
+        let backup_directory = settings.get_directory().join("backup").join(folder_name);
+
+        reporter.set("puzzle_id", puzzle_id);
+        reporter.set("directory", &directory);
+
+        if self.arguments.list {
+            self.list_backups(reporter, &backup_directory);
+            return;
+        }
+
+        // Find the backup to restore before touching the working folder.
+        let restore_directory = match &self.arguments.restore {
+            Some(name) => {
+                // Only a folder directly inside the backup folder can be restored.
+                let restore_directory = backup_directory.join(name);
+                let mut components = Utf8Path::new(name).components();
+                let is_plain_name = matches!(
+                    (components.next(), components.next()),
+                    (Some(Utf8Component::Normal(_)), None)
+                );
+                if !is_plain_name || !restore_directory.is_dir() {
+                    self.errors.push(RukataError::Io(format!(
+                        "Backup `{}` does not exist",
+                        restore_directory
+                    )));
+                    return;
+                }
+                Some(restore_directory)
+            }
+            None => None,
+        };
+
+        // Back up the current attempt.
+        if directory.exists() {
+            let stage = match get_current_stage(puzzle_data) {
+                Ok(stage) => stage,
+                Err(error) => {
+                    self.errors.push(error);
+                    return;
+                }
+            };
+            let Some(new_backup_directory) = self.backup(&directory, &backup_directory, stage)
+            else {
+                return;
+            };
+            reporter.set("backup", &new_backup_directory);
+            reporter.print_white(format!(
+                "Backed up the current attempt to `{}`",
+                new_backup_directory
+            ));
+        } else if restore_directory.is_none() {
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` does not exist",
+                directory
+            )));
+            return;
+        }
+
+        match restore_directory {
+            // Restore the backup.
+            Some(restore_directory) => {
+                let stage =
+                    read_stage_marker(&restore_directory).min(puzzle_data.get_stage_count() - 1);
+                if let Err(e) = fs::rename(&restore_directory, &directory) {
+                    self.errors.push(RukataError::Io(format!(
+                        "Failed to move `{}` to `{}` with error: {}",
+                        restore_directory, directory, e
+                    )));
+                    return;
+                }
+
+                // The backup may be at another stage than the attempt it replaced.
+                if let Some(error) = update_progress(|progress| {
+                    progress.get_mut_puzzle(puzzle_id).set_stage(stage);
+                }) {
+                    self.errors.push(error);
+                    return;
+                }
+
+                reporter.set("restored", &restore_directory);
+                reporter.print_green(format!(
+                    "Restored puzzle {} from `{}`",
+                    puzzle_id, restore_directory
+                ));
+            }
+
+            // Write the starter files.
+            None => {
+                if let Err(e) = fs::create_dir_all(&directory) {
+                    self.errors.push(RukataError::Io(format!(
+                        "Failed to create directory `{}` with error: {}",
+                        directory, e
+                    )));
+                    return;
+                }
+
+                self.errors = populate_puzzle_directory(puzzle_data, &directory);
+                if !self.errors.is_empty() {
+                    return;
+                }
+
+                // The starter files are those of the first stage.
+                if let Some(error) = update_progress(|progress| {
+                    progress.get_mut_puzzle(puzzle_id).set_stage(0);
+                }) {
+                    self.errors.push(error);
+                    return;
+                }
+
+                reporter.print_green(format!("Reset puzzle {} to its starter files", puzzle_id));
+            }
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl ResetCommand {
+    pub fn new(arguments: ResetArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn backup(
+        &mut self,
+        directory: &Utf8PathBuf,
+        backup_directory: &Utf8PathBuf,
+        stage: usize,
+    ) -> Option<Utf8PathBuf> {
+        if let Err(e) = fs::create_dir_all(backup_directory) {
+            self.errors.push(RukataError::Io(format!(
+                "Failed to create directory `{}` with error: {}",
+                backup_directory, e
+            )));
+            return None;
+        }
+
+        // Name the backup after the current time, avoiding any collisions.
+        let timestamp = Local::now().format("%Y%m%d-%H%M%S").to_string();
+        let mut new_backup_directory = backup_directory.join(&timestamp);
+        let mut suffix = 1;
+        while new_backup_directory.exists() {
+            new_backup_directory = backup_directory.join(format!("{}-{}", timestamp, suffix));
+            suffix += 1;
+        }
+
+        // Restoring the backup has to put the progress back at its stage.
+        if let Some(error) = write_stage_marker(directory, stage) {
+            self.errors.push(error);
+            return None;
+        }
+
+        if let Err(e) = fs::rename(directory, &new_backup_directory) {
+            self.errors.push(RukataError::Io(format!(
+                "Failed to move `{}` to `{}` with error: {}",
+                directory, new_backup_directory, e
+            )));
+            return None;
+        }
+
+        Some(new_backup_directory)
+    }
+
+    fn list_backups(&mut self, reporter: &mut Reporter, backup_directory: &Utf8PathBuf) {
+        let mut backups = Vec::new();
+        if backup_directory.exists() {
+            match backup_directory.read_dir_utf8() {
+                Ok(read_directory) => {
+                    for entry in read_directory.flatten() {
+                        if entry.path().is_dir() {
+                            backups.push(entry.file_name().to_string());
+                        }
+                    }
+                }
+                Err(e) => {
+                    self.errors.push(RukataError::Io(format!(
+                        "Failed to read directory `{}` with error: {}",
+                        backup_directory, e
+                    )));
+                    return;
+                }
+            }
+        }
+
+        backups.sort();
+        reporter.set("backups", &backups);
+
+        if backups.is_empty() {
+            reporter.print_white(format!(
+                "No backups found for puzzle {}",
+                self.arguments.puzzle_id
+            ));
+            return;
+        }
+
+        reporter.print_cyan_title(format!("Backups in `{}`:", backup_directory));
+        for backup in backups {
+            reporter.print_white(format!("- {}", backup));
+        }
+    }
+}
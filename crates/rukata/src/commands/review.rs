@@ -0,0 +1,182 @@
+use crate::argument_builder::ReviewArguments;
+use crate::command::{get_progress, Command};
+use crate::common::{format_timestamp, get_puzzle_folder_name, start_new_attempt};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use chrono::{DateTime, Utc};
+use rukata_progress::versions::v1::{Progress, ReviewSchedule};
+use rukata_puzzle_data::get_file_data;
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+const TABLE_HEADERS: &[&str; 6] = &["ID", "Title", "Last review", "Grade", "Interval", "Due"];
+
+pub struct ReviewCommand {
+    arguments: ReviewArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for ReviewCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        let progress_handler = match get_progress() {
+            Ok(handler) => handler,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+        let progress = progress_handler.get_progress();
+
+        let timestamp = Utc::now();
+        Self::print_schedule(reporter, progress, timestamp);
+
+        let due_reviews = progress.get_due_reviews(timestamp);
+        if due_reviews.is_empty() {
+            reporter.set("regenerated", Vec::<serde_json::Value>::new());
+            return;
+        }
+
+        if self.arguments.list {
+            reporter.print_white(format!(
+                "{} puzzle(s) are due for review, run `rukata review` to generate them",
+                due_reviews.len()
+            ));
+            return;
+        }
+
+        // Give each due puzzle a fresh start, leaving any review already under way alone.
+        let mut regenerated = Vec::new();
+        for puzzle_id in due_reviews {
+            let Some(puzzle_data) = get_file_data(puzzle_id) else {
+                continue;
+            };
+
+            let folder_name = get_puzzle_folder_name(puzzle_data);
+            let directory = settings
+                .get_directory()
+                .join("working")
+                .join(folder_name.clone());
+            let attempts_directory = settings.get_directory().join("attempts").join(folder_name);
+
+            if Self::is_under_way(progress, puzzle_id) {
+                reporter.print_white(format!(
+                    "Puzzle {} is already being reviewed in `{}`",
+                    puzzle_id, directory
+                ));
+                continue;
+            }
+
+            match start_new_attempt(puzzle_data, &directory, &attempts_directory) {
+                Ok(archived) => {
+                    reporter.print_green(format!(
+                        "Generated puzzle {} for review in `{}`",
+                        puzzle_id, directory
+                    ));
+                    regenerated.push(json!({
+                        "id": puzzle_id,
+                        "directory": directory,
+                        "archived": archived.map(|archived| archived.directory),
+                    }));
+                }
+                Err(errors) => self.errors.extend(errors),
+            }
+        }
+
+        reporter.set("regenerated", regenerated);
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl ReviewCommand {
+    pub fn new(arguments: ReviewArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    /// Whether the puzzle was already started again since its latest review.
+    fn is_under_way(progress: &Progress, puzzle_id: u16) -> bool {
+        let Some(puzzle_progress) = progress.get_puzzle(puzzle_id) else {
+            return false;
+        };
+
+        match (
+            puzzle_progress.get_current_run(),
+            puzzle_progress.get_review(),
+        ) {
+            (Some(run), Some(review)) => run.get_started() > review.get_last_reviewed(),
+            _ => false,
+        }
+    }
+
+    fn print_schedule(reporter: &mut Reporter, progress: &Progress, timestamp: DateTime<Utc>) {
+        let mut reviews: Vec<(u16, &ReviewSchedule)> = progress
+            .get_puzzles()
+            .iter()
+            .filter_map(|(puzzle_id, puzzle_progress)| {
+                Some((*puzzle_id, puzzle_progress.get_review()?))
+            })
+            .collect();
+        reviews.sort_by_key(|(puzzle_id, review)| (*review.get_due(), *puzzle_id));
+
+        let mut rows = Vec::new();
+        let mut reviews_json = Vec::new();
+        for (puzzle_id, review) in reviews {
+            let title = get_file_data(puzzle_id).map(|puzzle_data| puzzle_data.get_title());
+            let due = if review.is_due(timestamp) {
+                "now".to_string()
+            } else {
+                format_timestamp(Some(review.get_due()))
+            };
+
+            rows.push(vec![
+                format!("p{:0>5}", puzzle_id),
+                title.unwrap_or_default().to_string(),
+                format_timestamp(Some(review.get_last_reviewed())),
+                format!("{}/5", review.get_last_quality()),
+                format!("{} day(s)", review.get_interval_days()),
+                due,
+            ]);
+            reviews_json.push(json!({
+                "id": puzzle_id,
+                "title": title,
+                "is_due": review.is_due(timestamp),
+                "review": review,
+            }));
+        }
+
+        reporter.set("reviews", reviews_json);
+
+        if rows.is_empty() {
+            reporter.print_white(
+                "No puzzles are scheduled for review yet, complete a generated puzzle to schedule it.",
+            );
+            return;
+        }
+
+        reporter.print_cyan_title("Review schedule:");
+        reporter.print_table(TABLE_HEADERS, &rows);
+    }
+}
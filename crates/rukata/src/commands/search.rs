@@ -0,0 +1,103 @@
+use crate::argument_builder::SearchArguments;
+use crate::command::Command;
+use crate::error::RukataError;
+use crate::reporter::{highlight, Reporter};
+use rukata_puzzle_data::search::{search, SearchResult};
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+pub struct SearchCommand {
+    arguments: SearchArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for SearchCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let query = self.arguments.query.join(" ");
+        let mut results = search(&query);
+        results.truncate(self.arguments.limit);
+
+        Self::print_human(reporter, &query, &results);
+        reporter.set("query", &query);
+        reporter.set("results", Self::to_json(&results));
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl SearchCommand {
+    pub fn new(arguments: SearchArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn print_human(reporter: &Reporter, query: &str, results: &[SearchResult]) {
+        if results.is_empty() {
+            reporter.print_white(format!("No puzzles match `{}`.", query));
+            return;
+        }
+
+        for result in results {
+            let puzzle_data = result.get_puzzle_data();
+            reporter.print_cyan_title(format!(
+                "p{:0>5} - {} (score {})",
+                puzzle_data.get_id(),
+                puzzle_data.get_title(),
+                result.get_score()
+            ));
+
+            for search_match in result.get_matches() {
+                reporter.print_styled(format!(
+                    "  {}: {}",
+                    search_match.get_field(),
+                    highlight(search_match.get_snippet(), search_match.get_highlights())
+                ));
+            }
+        }
+    }
+
+    fn to_json(results: &[SearchResult]) -> Vec<serde_json::Value> {
+        results
+            .iter()
+            .map(|result| {
+                let puzzle_data = result.get_puzzle_data();
+                let matches: Vec<serde_json::Value> = result
+                    .get_matches()
+                    .iter()
+                    .map(|search_match| {
+                        let highlights: Vec<[usize; 2]> = search_match
+                            .get_highlights()
+                            .iter()
+                            .map(|range| [range.start, range.end])
+                            .collect();
+
+                        json!({
+                            "field": search_match.get_field().to_string(),
+                            "snippet": search_match.get_snippet(),
+                            "highlights": highlights,
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "id": puzzle_data.get_id(),
+                    "title": puzzle_data.get_title(),
+                    "score": result.get_score(),
+                    "matches": matches,
+                })
+            })
+            .collect()
+    }
+}
@@ -1,12 +1,14 @@
 use crate::argument_builder::SettingsArguments;
 use crate::command::Command;
-use crate::common::{print_cyan_title, print_white};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use rukata_settings::versions::v1::CargoSettings;
 use rukata_settings::SettingsHandler;
 
 pub struct SettingsCommand {
     arguments: SettingsArguments,
     settings: Option<SettingsHandler>,
-    errors: Vec<String>,
+    errors: Vec<RukataError>,
 }
 
 impl Command for SettingsCommand {
@@ -16,19 +18,21 @@ impl Command for SettingsCommand {
 
     fn initialize(&mut self) {}
 
-    fn execute(&mut self) {
+    fn execute(&mut self, reporter: &mut Reporter) {
         let settings_handler = self.settings.as_mut().expect("Failed to set settings");
 
+        reporter.set("path", settings_handler.get_path());
+
         // Print current settings path.
-        print_cyan_title("Current path for settings file:");
-        print_white(settings_handler.get_path());
+        reporter.print_cyan_title("Current path for settings file:");
+        reporter.print_white(settings_handler.get_path());
 
         // Get the settings
         let settings = settings_handler.get_mut_settings();
 
         // Print current settings.
-        print_cyan_title("Current settings data:");
-        print_white(format!("{}", settings));
+        reporter.print_cyan_title("Current settings data:");
+        reporter.print_white(format!("{}", settings));
 
         let mut has_changed = false;
 
@@ -38,20 +42,76 @@ impl Command for SettingsCommand {
             settings.set_directory(directory);
         }
 
+        // Update the limits if provided.
+        if let Some(test_timeout) = self.arguments.test_timeout {
+            has_changed = true;
+            settings.set_test_timeout(Some(test_timeout));
+        }
+
+        if let Some(memory_limit) = self.arguments.memory_limit {
+            has_changed = true;
+            settings.set_memory_limit(Some(memory_limit));
+        }
+
+        if let Some(cpu_limit) = self.arguments.cpu_limit {
+            has_changed = true;
+            settings.set_cpu_limit(Some(cpu_limit));
+        }
+
+        // Update how cargo is run if provided.
+        let cargo = settings.get_mut_cargo();
+        if self.arguments.reset_cargo {
+            has_changed = true;
+            *cargo = CargoSettings::default();
+        }
+
+        if let Some(binary) = &self.arguments.cargo_binary {
+            has_changed = true;
+            cargo.set_binary(Some(binary.clone()));
+        }
+
+        if let Some(toolchain) = &self.arguments.toolchain {
+            has_changed = true;
+            cargo.set_toolchain(Some(toolchain.clone()));
+        }
+
+        if let Some(offline) = self.arguments.offline {
+            has_changed = true;
+            cargo.set_offline(offline);
+        }
+
+        if let Some(target_directory) = &self.arguments.target_directory {
+            has_changed = true;
+            cargo.set_target_directory(Some(target_directory.clone()));
+        }
+
+        if !self.arguments.cargo_arguments.is_empty() {
+            has_changed = true;
+            cargo.set_arguments(self.arguments.cargo_arguments.clone());
+        }
+
+        if !self.arguments.cargo_environment.is_empty() {
+            has_changed = true;
+            cargo.set_environment(self.arguments.cargo_environment.iter().cloned().collect());
+        }
+
+        reporter.set("settings", &*settings);
+        reporter.set("changed", has_changed);
+
         // If the settings have changed show the user and update file.
         if has_changed {
             // Print current settings.
-            print_cyan_title("New settings data:");
-            print_white(format!("{}", settings));
+            reporter.print_cyan_title("New settings data:");
+            reporter.print_white(format!("{}", settings));
 
             // Save the settings.
             if let Err(e) = settings_handler.save() {
-                self.errors.push(e);
+                self.errors.push(RukataError::Io(e));
             }
         }
     }
 
-    fn get_errors(&self) -> Vec<String> {
+    fn get_errors(&self) -> Vec<RukataError> {
         self.errors.clone()
     }
 }
@@ -1,6 +1,8 @@
 use crate::argument_builder::GeneralArguments;
 use crate::command::Command;
-use crate::common::generate_file;
+use crate::common::{generate_file, get_puzzle_folder_name};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
 use crate::validation::validate_settings;
 use rukata_puzzle_data::{get_file_data, PuzzleData};
 use rukata_settings::SettingsHandler;
@@ -9,7 +11,7 @@ use std::fs;
 pub struct SolutionCommand {
     arguments: GeneralArguments,
     settings: Option<SettingsHandler>,
-    errors: Vec<String>,
+    errors: Vec<RukataError>,
 }
 
 impl Command for SolutionCommand {
@@ -19,7 +21,7 @@ impl Command for SolutionCommand {
 
     fn initialize(&mut self) {}
 
-    fn execute(&mut self) {
+    fn execute(&mut self, reporter: &mut Reporter) {
         let settings_handler = self.settings.as_ref().expect("Failed to set settings");
 
         // Get the settings
@@ -34,28 +36,31 @@ impl Command for SolutionCommand {
         let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
             Some(data) => data,
             None => {
-                self.errors
-                    .push(format!("Puzzle ID is not valid {}", puzzle_id));
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
                 return;
             }
         };
 
+        reporter.set("puzzle_id", puzzle_id);
+
         // Generate the main folder.
-        let title = puzzle_data.get_title();
-        let folder_name = format!("p{:0>5} - {}", puzzle_id, title);
+        let folder_name = get_puzzle_folder_name(puzzle_data);
 
         let directory = settings.get_directory().join("solution").join(folder_name);
+        reporter.set("directory", &directory);
         if directory.exists() {
-            self.errors
-                .push(format!("Directory `{}` already exists", directory));
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` already exists",
+                directory
+            )));
         }
 
         if !directory.exists() {
             if let Err(e) = fs::create_dir_all(directory.clone()) {
-                self.errors.push(format!(
+                self.errors.push(RukataError::Io(format!(
                     "Failed to create directory `{}` with error: {}",
                     directory, e
-                ));
+                )));
             }
         }
 
@@ -72,7 +77,7 @@ impl Command for SolutionCommand {
         }
     }
 
-    fn get_errors(&self) -> Vec<String> {
+    fn get_errors(&self) -> Vec<RukataError> {
         self.errors.clone()
     }
 }
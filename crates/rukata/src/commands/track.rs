@@ -0,0 +1,245 @@
+use crate::argument_builder::TrackCommands;
+use crate::command::{get_progress, Command};
+use crate::common::{get_puzzle_folder_name, start_new_attempt};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::validation::validate_settings;
+use rukata_progress::versions::v1::Progress;
+use rukata_puzzle_data::{get_file_data, get_track, get_tracks, PuzzleTrack};
+use rukata_settings::versions::v1::Settings;
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+
+const LIST_TABLE_HEADERS: &[&str; 4] = &["ID", "Title", "Puzzles", "Completed"];
+
+const SHOW_TABLE_HEADERS: &[&str; 5] = &["Step", "ID", "Title", "Difficulty", "State"];
+
+pub struct TrackCommand {
+    arguments: TrackCommands,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for TrackCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        let progress_handler = match get_progress() {
+            Ok(handler) => handler,
+            Err(e) => {
+                self.errors.push(e);
+                return;
+            }
+        };
+        let progress = progress_handler.get_progress();
+
+        let track_id = match &self.arguments {
+            TrackCommands::List => {
+                Self::list_tracks(reporter, progress);
+                return;
+            }
+            TrackCommands::Show(arguments) | TrackCommands::Start(arguments) => {
+                arguments.track_id.as_str()
+            }
+        };
+
+        let Some(track) = get_track(track_id) else {
+            self.errors.push(RukataError::Io(format!(
+                "Track `{}` does not exist, run `rukata track list` to see the tracks",
+                track_id
+            )));
+            return;
+        };
+
+        reporter.set("track_id", track.get_id());
+        match self.arguments {
+            TrackCommands::Show(_) => Self::show_track(reporter, progress, track),
+            _ => self.errors = Self::start_track(reporter, progress, settings, track),
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl TrackCommand {
+    pub fn new(arguments: TrackCommands) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn count_completed(progress: &Progress, track: &PuzzleTrack) -> usize {
+        track
+            .get_puzzles()
+            .iter()
+            .filter(|puzzle_id| progress.is_completed(**puzzle_id))
+            .count()
+    }
+
+    /// The first puzzle of the track that has not been completed.
+    fn get_next_puzzle(progress: &Progress, track: &PuzzleTrack) -> Option<u16> {
+        track
+            .get_puzzles()
+            .iter()
+            .copied()
+            .find(|puzzle_id| !progress.is_completed(*puzzle_id))
+    }
+
+    fn list_tracks(reporter: &mut Reporter, progress: &Progress) {
+        let mut rows = Vec::new();
+        let mut tracks_json = Vec::new();
+        for track in get_tracks() {
+            let completed = Self::count_completed(progress, track);
+            rows.push(vec![
+                track.get_id().to_string(),
+                track.get_title().to_string(),
+                track.get_puzzles().len().to_string(),
+                format!("{}/{}", completed, track.get_puzzles().len()),
+            ]);
+            tracks_json.push(json!({
+                "id": track.get_id(),
+                "title": track.get_title(),
+                "description": track.get_description(),
+                "puzzles": track.get_puzzles(),
+                "completed": completed,
+            }));
+        }
+
+        reporter.set("tracks", tracks_json);
+
+        if rows.is_empty() {
+            reporter.print_white("No tracks are available.");
+            return;
+        }
+
+        reporter.print_table(LIST_TABLE_HEADERS, &rows);
+    }
+
+    fn show_track(reporter: &mut Reporter, progress: &Progress, track: &PuzzleTrack) {
+        let mut rows = Vec::new();
+        let mut puzzles_json = Vec::new();
+        for (index, puzzle_id) in track.get_puzzles().iter().enumerate() {
+            let Some(puzzle_data) = get_file_data(*puzzle_id) else {
+                continue;
+            };
+
+            let state = progress.get_state(*puzzle_id);
+            rows.push(vec![
+                (index + 1).to_string(),
+                format!("p{:0>5}", puzzle_id),
+                puzzle_data.get_title().to_string(),
+                puzzle_data.get_difficulty().to_string(),
+                state.to_string(),
+            ]);
+            puzzles_json.push(json!({
+                "id": puzzle_id,
+                "title": puzzle_data.get_title(),
+                "difficulty": puzzle_data.get_difficulty().to_string(),
+                "state": state,
+            }));
+        }
+
+        let completed = Self::count_completed(progress, track);
+        let next_puzzle = Self::get_next_puzzle(progress, track);
+        reporter.set("title", track.get_title());
+        reporter.set("description", track.get_description());
+        reporter.set("puzzles", puzzles_json);
+        reporter.set("completed", completed);
+        reporter.set("next_puzzle", next_puzzle);
+
+        reporter.print_cyan_title(format!(
+            "{} ({}/{} completed)",
+            track.get_title(),
+            completed,
+            track.get_puzzles().len()
+        ));
+        reporter.print_white(track.get_description());
+        reporter.print_blank_line();
+        reporter.print_table(SHOW_TABLE_HEADERS, &rows);
+
+        match next_puzzle {
+            Some(puzzle_id) => reporter.print_white(format!(
+                "Run `rukata track start {}` to work on puzzle {}",
+                track.get_id(),
+                puzzle_id
+            )),
+            None => reporter.print_green("You have completed every puzzle in this track."),
+        }
+    }
+
+    fn start_track(
+        reporter: &mut Reporter,
+        progress: &Progress,
+        settings: &Settings,
+        track: &PuzzleTrack,
+    ) -> Vec<RukataError> {
+        let Some(puzzle_id) = Self::get_next_puzzle(progress, track) else {
+            reporter.set("puzzle_id", None::<u16>);
+            reporter.print_green(format!(
+                "You have completed every puzzle in the {} track.",
+                track.get_title()
+            ));
+            return Vec::new();
+        };
+        let Some(puzzle_data) = get_file_data(puzzle_id) else {
+            return vec![RukataError::UnknownPuzzle(puzzle_id)];
+        };
+
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings
+            .get_directory()
+            .join("working")
+            .join(folder_name.clone());
+        let attempts_directory = settings.get_directory().join("attempts").join(folder_name);
+
+        reporter.set("puzzle_id", puzzle_id);
+        reporter.set("directory", &directory);
+
+        let step = track
+            .get_puzzles()
+            .iter()
+            .position(|track_puzzle_id| *track_puzzle_id == puzzle_id)
+            .unwrap_or_default()
+            + 1;
+        reporter.print_cyan_title(format!(
+            "{} step {} of {}: p{:0>5} - {}",
+            track.get_title(),
+            step,
+            track.get_puzzles().len(),
+            puzzle_id,
+            puzzle_data.get_title()
+        ));
+
+        // The puzzle may already have been generated outside of the track.
+        if directory.exists() {
+            reporter.set("generated", false);
+            reporter.print_white(format!("Carry on with it in `{}`", directory));
+            return Vec::new();
+        }
+
+        if let Err(errors) = start_new_attempt(puzzle_data, &directory, &attempts_directory) {
+            return errors;
+        }
+
+        reporter.set("generated", true);
+        reporter.print_green(format!("Generated puzzle {} in `{}`", puzzle_id, directory));
+        Vec::new()
+    }
+}
@@ -0,0 +1,308 @@
+use crate::argument_builder::GeneralArguments;
+use crate::cargo::RUKATA_FOLDER;
+use crate::command::Command;
+use crate::commands::check::{finish_check, run_check, StepStatus};
+use crate::common::{get_current_stage, get_puzzle_folder_name, update_progress};
+use crate::error::RukataError;
+use crate::reporter::Reporter;
+use crate::test_report::{count_status, TestStatus};
+use crate::validation::validate_settings;
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{Local, Utc};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use rukata_puzzle_data::{get_file_data, PuzzleData};
+use rukata_settings::versions::v1::Settings;
+use rukata_settings::SettingsHandler;
+use serde_json::json;
+use std::sync::mpsc;
+use std::time::Duration;
+
+// Top level paths in the puzzle folder that are written by cargo and Rukata.
+const IGNORED_PATHS: &[&str; 3] = &["target", "Cargo.lock", RUKATA_FOLDER];
+
+// Cargo creates the target folder as `target` followed by random characters and then renames it.
+const TEMPORARY_TARGET_SUFFIX_LENGTH: usize = 6;
+
+// Time to wait for further changes before running the checks.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(300);
+
+pub struct WatchCommand {
+    arguments: GeneralArguments,
+    settings: Option<SettingsHandler>,
+    errors: Vec<RukataError>,
+}
+
+impl Command for WatchCommand {
+    fn set_settings(&mut self, settings: SettingsHandler) {
+        self.settings = Some(settings)
+    }
+
+    fn initialize(&mut self) {}
+
+    fn execute(&mut self, reporter: &mut Reporter) {
+        let settings_handler = self.settings.as_ref().expect("Failed to set settings");
+
+        // Get the settings
+        let settings = settings_handler.get_settings();
+        self.errors = validate_settings(settings);
+        if !self.errors.is_empty() {
+            return;
+        }
+
+        // Get the puzzle data.
+        let puzzle_id = self.arguments.puzzle_id;
+        let puzzle_data: &'static PuzzleData = match get_file_data(puzzle_id) {
+            Some(data) => data,
+            None => {
+                self.errors.push(RukataError::UnknownPuzzle(puzzle_id));
+                return;
+            }
+        };
+
+        // Get folder path.
+        let folder_name = get_puzzle_folder_name(puzzle_data);
+        let directory = settings.get_directory().join("working").join(folder_name);
+
+        // Check folder existing.
+        if !directory.exists() {
+            self.errors.push(RukataError::Io(format!(
+                "Directory `{}` does not exist",
+                directory
+            )));
+            return;
+        }
+
+        // Start watching before the first run so no change is missed.
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher = match notify::recommended_watcher(sender) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                self.errors.push(RukataError::Io(format!(
+                    "Failed to create file watcher with error: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(directory.as_std_path(), RecursiveMode::Recursive) {
+            self.errors.push(RukataError::Io(format!(
+                "Failed to watch directory `{}` with error: {}",
+                directory, e
+            )));
+            return;
+        }
+
+        reporter.print_cyan_title(format!("Watching `{}`, press Ctrl+C to stop.", directory));
+        Self::run(reporter, puzzle_data, settings, &directory);
+
+        loop {
+            // Wait for a relevant change.
+            match receiver.recv() {
+                Ok(event) => {
+                    if !Self::is_relevant(&directory, event) {
+                        continue;
+                    }
+                }
+                Err(_) => {
+                    self.errors.push(RukataError::Io(
+                        "File watcher stopped unexpectedly".to_string(),
+                    ));
+                    return;
+                }
+            }
+
+            // A change counts as working on the puzzle.
+            let puzzle_id = *puzzle_data.get_id();
+            if let Some(error) = update_progress(|progress| {
+                if let Some(puzzle_progress) = progress.get_mut_generated_puzzle(puzzle_id) {
+                    puzzle_progress.record_activity(Utc::now());
+                }
+            }) {
+                let time = Local::now().format("%H:%M:%S");
+                reporter.print_red(format!("[{}] {}", time, error));
+            }
+
+            // Let the burst of changes from a save settle.
+            while receiver.recv_timeout(DEBOUNCE_DURATION).is_ok() {}
+
+            Self::run(reporter, puzzle_data, settings, &directory);
+        }
+    }
+
+    fn get_errors(&self) -> Vec<RukataError> {
+        self.errors.clone()
+    }
+}
+
+impl WatchCommand {
+    pub fn new(arguments: GeneralArguments) -> Self {
+        Self {
+            arguments,
+            settings: None,
+            errors: vec![],
+        }
+    }
+
+    fn is_relevant(directory: &Utf8Path, event: notify::Result<Event>) -> bool {
+        let event = match event {
+            Ok(event) => event,
+            Err(_) => return false,
+        };
+
+        if matches!(event.kind, EventKind::Access(_)) {
+            return false;
+        }
+
+        event.paths.iter().any(|path| {
+            let relative_path = path.strip_prefix(directory).unwrap_or(path);
+            let first_component = match relative_path.components().next() {
+                Some(component) => component.as_os_str().to_string_lossy(),
+                None => return false,
+            };
+
+            !Self::is_ignored(&first_component)
+        })
+    }
+
+    /// Whether changes under the top level path `name` are made by cargo or Rukata.
+    fn is_ignored(name: &str) -> bool {
+        IGNORED_PATHS.contains(&name)
+            || name.strip_prefix("target").is_some_and(|suffix| {
+                suffix.len() == TEMPORARY_TARGET_SUFFIX_LENGTH
+                    && suffix.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+    }
+
+    fn run(
+        reporter: &Reporter,
+        puzzle_data: &PuzzleData,
+        settings: &Settings,
+        directory: &Utf8PathBuf,
+    ) {
+        let puzzle_id = *puzzle_data.get_id();
+        let time = Local::now().format("%H:%M:%S");
+        reporter.print_white(format!("[{}] Checking puzzle {}...", time, puzzle_id));
+
+        let stage = match get_current_stage(puzzle_data) {
+            Ok(stage) => stage,
+            Err(error) => {
+                reporter.print_red(format!("[{}] {}", time, error));
+                return;
+            }
+        };
+
+        let report = run_check(puzzle_data, directory, settings, stage, false);
+        let mut errors = report.errors;
+        let (run_time, unlocked) = finish_check(puzzle_data, directory, stage, &mut errors);
+
+        // Each run is written as its own line of JSON.
+        reporter.emit(json!({
+            "command": "watch",
+            "status": if errors.is_empty() { "success" } else { "failure" },
+            "puzzle_id": puzzle_id,
+            "timestamp": Local::now().to_rfc3339(),
+            "diagnostics": report.diagnostics,
+            "tests": report.tests,
+            "hidden_tests": report.hidden_tests,
+            "steps": report.steps,
+            "compile_fail": report.compile_fail,
+            "mutants": report.mutants,
+            "stopped": report.stopped,
+            "lints": report.lints,
+            "formatting": report.formatting,
+            "log_paths": report.log_paths,
+            "time": run_time,
+            "unlocked": unlocked,
+            "errors": errors,
+        }));
+
+        let time = Local::now().format("%H:%M:%S");
+        let summary = format!(
+            "{} passed, {} failed, {} ignored",
+            count_status(&report.tests, TestStatus::Passed),
+            count_status(&report.tests, TestStatus::Failed),
+            count_status(&report.tests, TestStatus::Ignored)
+        );
+        if let (true, Some(unlocked)) = (errors.is_empty(), &unlocked) {
+            reporter.print_green(format!(
+                "[{}] PASS ({}) - unlocked stage {} of {}: {}",
+                time, summary, unlocked.stage, unlocked.stage_count, unlocked.title
+            ));
+            reporter.print_white(format!("  {}", unlocked.description));
+            for file in &unlocked.files {
+                reporter.print_white(format!("  Added `{}`", file));
+            }
+        } else if errors.is_empty() {
+            let run_time = run_time
+                .map(|run_time| format!(" {}", run_time))
+                .unwrap_or_default();
+            reporter.print_green(format!(
+                "[{}] PASS ({}) - puzzle {} is complete{}",
+                time, summary, puzzle_id, run_time
+            ));
+        } else {
+            reporter.print_red(format!("[{}] FAIL ({})", time, summary));
+            for diagnostic in report.diagnostics.iter().chain(&report.lints) {
+                reporter.print_red(format!(
+                    "  {} ({})",
+                    diagnostic.get_headline(),
+                    diagnostic.get_location().unwrap_or_default()
+                ));
+            }
+            for test in &report.tests {
+                if test.status == TestStatus::Failed {
+                    reporter.print_red(format!("  {}", test.name));
+                }
+            }
+            for step in &report.steps {
+                if step.status == StepStatus::Failed {
+                    reporter.print_red(format!("  {}", step.step));
+                }
+            }
+            for result in &report.compile_fail {
+                if !result.passed {
+                    reporter.print_red(format!("  {} ({})", result.path, result.code));
+                }
+            }
+            for result in &report.mutants {
+                if !result.caught {
+                    reporter.print_red(format!("  {} survived", result.name));
+                }
+            }
+            for test in &report.hidden_tests {
+                if test.status == TestStatus::Failed {
+                    reporter.print_red(format!("  {} (hidden)", test.name));
+                }
+            }
+            for error in errors {
+                reporter.print_white(format!("- {}", error));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::ModifyKind;
+
+    fn is_relevant(path: &str) -> bool {
+        let event = Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.into());
+        WatchCommand::is_relevant(Utf8Path::new("/puzzle"), Ok(event))
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        assert!(is_relevant("/puzzle/src/lib.rs"));
+        assert!(!is_relevant("/puzzle/target/debug/build"));
+        assert!(!is_relevant("/puzzle/Cargo.lock"));
+        assert!(!is_relevant("/puzzle/.rukata/check.log"));
+        assert!(!is_relevant("/puzzle/targetLB3n4T"));
+
+        // Only the whole name of the top level path is ignored.
+        assert!(is_relevant("/puzzle/targets.rs"));
+        assert!(is_relevant("/puzzle/Cargo.lock.bak"));
+        assert!(is_relevant("/puzzle/src/target/mod.rs"));
+    }
+}
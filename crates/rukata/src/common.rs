@@ -1,56 +1,537 @@
-use camino::Utf8PathBuf;
-use console::Style;
-use lazy_static::lazy_static;
+use crate::cargo::RUKATA_FOLDER;
+use crate::command::get_progress;
+use crate::error::RukataError;
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Local, Utc};
+use rukata_progress::versions::v1::{Progress, PuzzleProgress};
+use rukata_puzzle_data::PuzzleData;
 use std::fs;
-use std::ops::Deref;
 
-lazy_static! {
-    static ref CYAN_TITLE: Style = Style::new().bold().cyan();
-    static ref RED_TITLE: Style = Style::new().bold().red();
-    static ref WHITE: Style = Style::new().white();
-    static ref GREEN: Style = Style::new().green();
-}
+// File in the Rukata folder of a backup holding the stage it was at.
+static STAGE_MARKER_NAME: &str = "stage";
 
-fn print_common(display: &str, style: &Style) {
-    println!("{}", style.apply_to(display));
+pub fn format_timestamp(timestamp: Option<&DateTime<Utc>>) -> String {
+    match timestamp {
+        Some(timestamp) => timestamp
+            .with_timezone(&Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string(),
+        None => "-".to_string(),
+    }
 }
 
-pub fn print_cyan_title<S: AsRef<str>>(display: S) {
-    println!();
-    print_common(display.as_ref(), CYAN_TITLE.deref());
+/// Formats a number of seconds such as `1h 02m 03s` or `4m 12s`.
+pub fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{}h {:0>2}m {:0>2}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:0>2}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
 }
 
-pub fn print_red_title<S: AsRef<str>>(display: S) {
-    println!();
-    print_common(display.as_ref(), RED_TITLE.deref());
-}
+pub fn update_progress<F: FnOnce(&mut Progress)>(update: F) -> Option<RukataError> {
+    let mut progress_handler = match get_progress() {
+        Ok(handler) => handler,
+        Err(e) => return Some(e),
+    };
 
-pub fn print_white<S: AsRef<str>>(display: S) {
-    print_common(display.as_ref(), WHITE.deref());
-}
+    update(progress_handler.get_mut_progress());
 
-pub fn print_green<S: AsRef<str>>(display: S) {
-    print_common(display.as_ref(), GREEN.deref());
+    progress_handler.save().err().map(RukataError::Io)
 }
 
-pub fn generate_file(file_path: Utf8PathBuf, file_data: &[u8]) -> Option<String> {
+pub fn generate_file(file_path: Utf8PathBuf, file_data: &[u8]) -> Option<RukataError> {
     if let Some(parent) = file_path.parent() {
         if let Err(e) = fs::create_dir_all(parent) {
-            return Some(format!(
+            return Some(RukataError::Io(format!(
                 "Failed to create directory `{}` with error: {}",
                 parent, e
-            ));
+            )));
         }
     } else {
-        return Some(format!("Failed to find parent for file `{}`", file_path));
+        return Some(RukataError::Io(format!(
+            "Failed to find parent for file `{}`",
+            file_path
+        )));
     }
 
     if let Err(e) = fs::write(&file_path, file_data) {
-        return Some(format!(
+        return Some(RukataError::Io(format!(
             "Failed to create file `{}` with error: {}",
             file_path, e
-        ));
+        )));
     }
 
     None
 }
+
+/// Copies a folder recursively, skipping the top level entries named in `ignored`.
+pub fn copy_directory(
+    source: &Utf8Path,
+    destination: &Utf8Path,
+    ignored: &[&str],
+) -> Result<(), RukataError> {
+    if let Err(e) = fs::create_dir_all(destination) {
+        return Err(RukataError::Io(format!(
+            "Failed to create directory `{}` with error: {}",
+            destination, e
+        )));
+    }
+
+    let read_directory = source.read_dir_utf8().map_err(|e| {
+        RukataError::Io(format!(
+            "Failed to read directory `{}` with error: {}",
+            source, e
+        ))
+    })?;
+
+    for entry in read_directory {
+        let entry = entry.map_err(|e| {
+            RukataError::Io(format!(
+                "Failed to read entry in `{}` with error: {}",
+                source, e
+            ))
+        })?;
+
+        if ignored.contains(&entry.file_name()) {
+            continue;
+        }
+
+        let destination_path = destination.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_directory(entry.path(), &destination_path, &[])?;
+        } else if let Err(e) = fs::copy(entry.path(), &destination_path) {
+            return Err(RukataError::Io(format!(
+                "Failed to copy `{}` to `{}` with error: {}",
+                entry.path(),
+                destination_path,
+                e
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn get_puzzle_folder_name(puzzle_data: &PuzzleData) -> String {
+    format!(
+        "p{:0>5} - {}",
+        puzzle_data.get_id(),
+        puzzle_data.get_title()
+    )
+}
+
+pub fn populate_puzzle_directory(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+) -> Vec<RukataError> {
+    let mut errors = Vec::new();
+
+    // Populate the main folder.
+    for file_data in puzzle_data.get_base_files() {
+        let file_path = directory.join(file_data.get_relative_path());
+        if let Some(error) = generate_file(file_path, file_data.get_raw_data()) {
+            errors.push(error);
+        }
+    }
+
+    if !errors.is_empty() {
+        return errors;
+    }
+
+    // Mark files read-only.
+    let read_only_files = puzzle_data.get_read_only_file_paths();
+    for file_name in read_only_files {
+        if let Some(error) = set_read_only(&directory.join(file_name), true) {
+            errors.push(error);
+        }
+    }
+
+    errors
+}
+
+fn set_read_only(file_path: &Utf8Path, read_only: bool) -> Option<RukataError> {
+    match file_path.metadata() {
+        Ok(metadata) => {
+            let mut permissions = metadata.permissions();
+            permissions.set_readonly(read_only);
+            if let Err(e) = fs::set_permissions(file_path, permissions) {
+                return Some(RukataError::Io(format!(
+                    "Failed to modify metadata for `{}` with error: {}",
+                    file_path, e
+                )));
+            }
+        }
+        Err(e) => {
+            return Some(RukataError::Io(format!(
+                "Failed to read metadata for `{}` with error: {}",
+                file_path, e
+            )));
+        }
+    }
+
+    None
+}
+
+/// The stage of the working puzzle recorded in the progress store, counting from 0.
+pub fn get_current_stage(puzzle_data: &PuzzleData) -> Result<usize, RukataError> {
+    let progress_handler = get_progress()?;
+    let stage = progress_handler
+        .get_progress()
+        .get_puzzle(*puzzle_data.get_id())
+        .map_or(0, PuzzleProgress::get_stage);
+
+    Ok(stage.min(puzzle_data.get_stage_count() - 1))
+}
+
+/// Saves the stage of a working puzzle inside its Rukata folder, so a copy of the folder such as a
+/// backup knows which stage it is at.
+pub fn write_stage_marker(directory: &Utf8Path, stage: usize) -> Option<RukataError> {
+    generate_file(
+        directory.join(RUKATA_FOLDER).join(STAGE_MARKER_NAME),
+        stage.to_string().as_bytes(),
+    )
+}
+
+/// The stage saved by `write_stage_marker`, folders saved without one are at the first stage.
+pub fn read_stage_marker(directory: &Utf8Path) -> usize {
+    fs::read_to_string(directory.join(RUKATA_FOLDER).join(STAGE_MARKER_NAME))
+        .ok()
+        .and_then(|stage| stage.trim().parse().ok())
+        .unwrap_or_default()
+}
+
+/// Writes the starter files of a stage of a multi-stage puzzle, returning the paths written.
+///
+/// Read-only files are always written, replacing those of earlier stages. Starter code the user
+/// is meant to change is only written when the file is missing or was read-only until now.
+pub fn populate_stage(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8Path,
+    stage: usize,
+) -> Result<Vec<Utf8PathBuf>, Vec<RukataError>> {
+    let Some(puzzle_stage) = stage
+        .checked_sub(1)
+        .and_then(|index| puzzle_data.get_stages().get(index))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let previous_read_only_paths: Vec<&str> = puzzle_data
+        .get_stage_read_only_files(stage - 1)
+        .iter()
+        .map(|file_data| file_data.get_relative_path())
+        .collect();
+
+    let mut errors = Vec::new();
+    let mut written = Vec::new();
+    for file_data in puzzle_stage.get_starter_files() {
+        let relative_path = file_data.get_relative_path();
+        let file_path = directory.join(relative_path);
+        let read_only = puzzle_stage
+            .get_read_only_file_paths()
+            .contains(&relative_path);
+        let was_read_only = previous_read_only_paths.contains(&relative_path);
+        if file_path.exists() {
+            if !read_only && !was_read_only {
+                continue;
+            }
+
+            // A read-only file has to be writable again before it is replaced.
+            if let Some(error) = set_read_only(&file_path, false) {
+                errors.push(error);
+                continue;
+            }
+        }
+
+        if let Some(error) = generate_file(file_path.clone(), file_data.get_raw_data()) {
+            errors.push(error);
+            continue;
+        }
+
+        if read_only {
+            if let Some(error) = set_read_only(&file_path, true) {
+                errors.push(error);
+                continue;
+            }
+        }
+
+        written.push(file_path);
+    }
+
+    if errors.is_empty() {
+        Ok(written)
+    } else {
+        errors.extend(remove_stage_files(puzzle_data, directory, stage, &written));
+        Err(errors)
+    }
+}
+
+/// Takes back the files `populate_stage` wrote for a stage, leaving the folder as it was at the
+/// stage before it.
+///
+/// Files that were read-only at the stage before are written again as they were, the others are
+/// removed.
+pub fn remove_stage_files(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8Path,
+    stage: usize,
+    written: &[Utf8PathBuf],
+) -> Vec<RukataError> {
+    let previous_read_only_files = puzzle_data.get_stage_read_only_files(stage.saturating_sub(1));
+
+    let mut errors = Vec::new();
+    for file_path in written {
+        if let Some(error) = set_read_only(file_path, false) {
+            errors.push(error);
+            continue;
+        }
+
+        let previous_file = previous_read_only_files
+            .iter()
+            .find(|file_data| directory.join(file_data.get_relative_path()) == *file_path);
+        match previous_file {
+            Some(file_data) => {
+                if let Some(error) = generate_file(file_path.clone(), file_data.get_raw_data()) {
+                    errors.push(error);
+                } else if let Some(error) = set_read_only(file_path, true) {
+                    errors.push(error);
+                }
+            }
+            None => {
+                if let Err(e) = fs::remove_file(file_path) {
+                    errors.push(RukataError::Io(format!(
+                        "Failed to remove file `{}` with error: {}",
+                        file_path, e
+                    )));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
+/// Name of the archive folder for an attempt number, such as `attempt-001`.
+pub fn get_attempt_name(number: usize) -> String {
+    format!("attempt-{:0>3}", number)
+}
+
+/// Finds the numbers of the attempts archived in the directory, in order.
+pub fn get_attempt_numbers(attempts_directory: &Utf8Path) -> Result<Vec<usize>, RukataError> {
+    let mut attempts = Vec::new();
+    if !attempts_directory.exists() {
+        return Ok(attempts);
+    }
+
+    let read_directory = attempts_directory.read_dir_utf8().map_err(|e| {
+        RukataError::Io(format!(
+            "Failed to read directory `{}` with error: {}",
+            attempts_directory, e
+        ))
+    })?;
+
+    for entry in read_directory.flatten() {
+        let number = entry
+            .file_name()
+            .strip_prefix("attempt-")
+            .and_then(|number| number.parse().ok());
+        if let Some(number) = number.filter(|_| entry.path().is_dir()) {
+            attempts.push(number);
+        }
+    }
+
+    attempts.sort_unstable();
+    Ok(attempts)
+}
+
+/// A working puzzle moved into the attempt archive.
+pub struct ArchivedAttempt {
+    pub number: usize,
+    pub directory: Utf8PathBuf,
+}
+
+/// Archives the working puzzle, when there is one, and writes fresh starter files in its place.
+///
+/// The new start is recorded in the progress store, keeping the earlier checks.
+pub fn start_new_attempt(
+    puzzle_data: &PuzzleData,
+    directory: &Utf8PathBuf,
+    attempts_directory: &Utf8Path,
+) -> Result<Option<ArchivedAttempt>, Vec<RukataError>> {
+    // Archive the current attempt under the next free number.
+    let mut archived = None;
+    if directory.exists() {
+        let attempts = get_attempt_numbers(attempts_directory).map_err(|error| vec![error])?;
+        let number = attempts.last().map_or(1, |last| last + 1);
+        let archive_directory = attempts_directory.join(get_attempt_name(number));
+        if let Err(e) = fs::create_dir_all(attempts_directory) {
+            return Err(vec![RukataError::Io(format!(
+                "Failed to create directory `{}` with error: {}",
+                attempts_directory, e
+            ))]);
+        }
+
+        if let Err(e) = fs::rename(directory, &archive_directory) {
+            return Err(vec![RukataError::Io(format!(
+                "Failed to move `{}` to `{}` with error: {}",
+                directory, archive_directory, e
+            ))]);
+        }
+
+        archived = Some(ArchivedAttempt {
+            number,
+            directory: archive_directory,
+        });
+    }
+
+    // Write the starter files.
+    if let Err(e) = fs::create_dir_all(directory) {
+        return Err(vec![RukataError::Io(format!(
+            "Failed to create directory `{}` with error: {}",
+            directory, e
+        ))]);
+    }
+
+    let errors = populate_puzzle_directory(puzzle_data, directory);
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let puzzle_id = *puzzle_data.get_id();
+    if let Some(error) = update_progress(|progress| {
+        let puzzle_progress = progress.get_mut_puzzle(puzzle_id);
+        if archived.is_some() {
+            puzzle_progress.record_redo(Utc::now());
+        } else {
+            puzzle_progress.record_generated(Utc::now());
+        }
+    }) {
+        return Err(vec![error]);
+    }
+
+    Ok(archived)
+}
+
+/// Collects when each file in the directory was last changed, skipping the top-level `ignored` entries.
+///
+/// Files that can not be read are skipped as the times are only used to estimate activity.
+pub fn get_modified_times(directory: &Utf8Path, ignored: &[&str]) -> Vec<DateTime<Utc>> {
+    let mut modified_times = Vec::new();
+    let Ok(read_directory) = directory.read_dir_utf8() else {
+        return modified_times;
+    };
+
+    for entry in read_directory.flatten() {
+        if ignored.contains(&entry.file_name()) {
+            continue;
+        }
+
+        if entry.path().is_dir() {
+            modified_times.extend(get_modified_times(entry.path(), &[]));
+        } else if let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) {
+            modified_times.push(DateTime::<Utc>::from(modified));
+        }
+    }
+
+    modified_times
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rukata_puzzle_data::get_file_data;
+    use tempfile::TempDir;
+
+    fn generate_stages(stage: usize) -> (TempDir, Utf8PathBuf) {
+        let temp_directory = TempDir::new().unwrap();
+        let directory = Utf8PathBuf::from_path_buf(temp_directory.path().to_path_buf()).unwrap();
+        let puzzle_data = get_file_data(3).unwrap();
+        assert!(populate_puzzle_directory(puzzle_data, &directory).is_empty());
+        for stage in 1..=stage {
+            populate_stage(puzzle_data, &directory, stage).unwrap();
+        }
+
+        (temp_directory, directory)
+    }
+
+    fn is_read_only(file_path: &Utf8Path) -> bool {
+        file_path.metadata().unwrap().permissions().readonly()
+    }
+
+    #[test]
+    fn test_populate_stage() {
+        let puzzle_data = get_file_data(3).unwrap();
+        let (_temp_directory, directory) = generate_stages(0);
+
+        // The first stage only has the starter files.
+        assert!(populate_stage(puzzle_data, &directory, 0)
+            .unwrap()
+            .is_empty());
+
+        let written = populate_stage(puzzle_data, &directory, 1).unwrap();
+        assert_eq!(written, vec![directory.join("tests/generic.rs")]);
+        assert!(is_read_only(&directory.join("tests/generic.rs")));
+
+        let mut written = populate_stage(puzzle_data, &directory, 2).unwrap();
+        written.sort();
+        assert_eq!(
+            written,
+            vec![
+                directory.join("src/squares.rs"),
+                directory.join("tests/iterator.rs")
+            ]
+        );
+        assert!(!is_read_only(&directory.join("src/squares.rs")));
+        assert!(is_read_only(&directory.join("tests/iterator.rs")));
+
+        // Stages past the last one have nothing to write.
+        assert!(populate_stage(puzzle_data, &directory, 3)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_populate_stage_keeps_user_code() {
+        let puzzle_data = get_file_data(3).unwrap();
+        let (_temp_directory, directory) = generate_stages(1);
+        let user_code = "// The user's code.\n";
+        fs::write(directory.join("src/lib.rs"), user_code).unwrap();
+        fs::write(directory.join("src/squares.rs"), user_code).unwrap();
+
+        let written = populate_stage(puzzle_data, &directory, 2).unwrap();
+        assert_eq!(written, vec![directory.join("tests/iterator.rs")]);
+        assert_eq!(
+            fs::read_to_string(directory.join("src/lib.rs")).unwrap(),
+            user_code
+        );
+        assert_eq!(
+            fs::read_to_string(directory.join("src/squares.rs")).unwrap(),
+            user_code
+        );
+    }
+
+    #[test]
+    fn test_remove_stage_files() {
+        let puzzle_data = get_file_data(3).unwrap();
+        let (_temp_directory, directory) = generate_stages(1);
+        let written = populate_stage(puzzle_data, &directory, 2).unwrap();
+
+        assert!(remove_stage_files(puzzle_data, &directory, 2, &written).is_empty());
+        assert!(!directory.join("src/squares.rs").exists());
+        assert!(!directory.join("tests/iterator.rs").exists());
+        assert!(is_read_only(&directory.join("tests/generic.rs")));
+    }
+
+    #[test]
+    fn test_stage_marker() {
+        let (_temp_directory, directory) = generate_stages(0);
+        assert_eq!(read_stage_marker(&directory), 0);
+
+        assert!(write_stage_marker(&directory, 2).is_none());
+        assert_eq!(read_stage_marker(&directory), 2);
+    }
+}
@@ -0,0 +1,102 @@
+use camino::Utf8PathBuf;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DiagnosticCode {
+    pub(crate) code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct DiagnosticSpan {
+    pub(crate) file_name: String,
+    pub(crate) line_start: usize,
+    pub(crate) column_start: usize,
+    pub(crate) is_primary: bool,
+    pub(crate) label: Option<String>,
+}
+
+/// A compiler diagnostic as emitted by `cargo --message-format=json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Diagnostic {
+    pub(crate) message: String,
+    pub(crate) code: Option<DiagnosticCode>,
+    pub(crate) level: String,
+    pub(crate) spans: Vec<DiagnosticSpan>,
+    pub(crate) children: Vec<Diagnostic>,
+}
+
+#[derive(Deserialize)]
+struct CargoTarget {
+    kind: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+    target: Option<CargoTarget>,
+    executable: Option<Utf8PathBuf>,
+}
+
+impl Diagnostic {
+    pub(crate) fn get_code(&self) -> Option<&str> {
+        self.code.as_ref().map(|code| code.code.as_str())
+    }
+
+    pub(crate) fn is_error(&self) -> bool {
+        self.level.starts_with("error")
+    }
+
+    pub(crate) fn get_primary_span(&self) -> Option<&DiagnosticSpan> {
+        self.spans
+            .iter()
+            .find(|span| span.is_primary)
+            .or(self.spans.first())
+    }
+
+    /// The first line of the diagnostic, such as `error[E0382]: borrow of moved value`.
+    pub(crate) fn get_headline(&self) -> String {
+        match self.get_code() {
+            Some(code) => format!("{}[{}]: {}", self.level, code, self.message),
+            None => format!("{}: {}", self.level, self.message),
+        }
+    }
+
+    pub(crate) fn get_location(&self) -> Option<String> {
+        self.get_primary_span().map(|span| {
+            format!(
+                "{}:{}:{}",
+                span.file_name, span.line_start, span.column_start
+            )
+        })
+    }
+}
+
+/// Parses the compiler errors out of the JSON lines written by cargo.
+pub(crate) fn parse_diagnostics(stdout: &str) -> Vec<Diagnostic> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|message| message.reason == "compiler-message")
+        .filter_map(|message| message.message)
+        .filter(|diagnostic| {
+            diagnostic.is_error() && !diagnostic.message.starts_with("aborting due to")
+        })
+        .collect()
+}
+
+/// Parses the paths of the binaries built by cargo out of its JSON lines.
+pub(crate) fn parse_executables(stdout: &str) -> Vec<Utf8PathBuf> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+        .filter(|message| message.reason == "compiler-artifact")
+        .filter(|message| {
+            message
+                .target
+                .as_ref()
+                .is_some_and(|target| target.kind.iter().any(|kind| kind == "bin"))
+        })
+        .filter_map(|message| message.executable)
+        .collect()
+}
@@ -0,0 +1,67 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// An error reported by a command.
+///
+/// Each kind maps to its own process exit code so scripts can tell them apart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RukataError {
+    /// The settings file or the Rukata directory can not be used.
+    InvalidSettings(String),
+    /// The puzzle ID does not exist.
+    UnknownPuzzle(u16),
+    /// A read-only puzzle file is missing or has been modified.
+    TamperedFile(String),
+    /// The attempt does not compile or its tests fail.
+    CargoFailure(String),
+    /// Reading or writing a file or running a process failed.
+    Io(String),
+}
+
+impl RukataError {
+    /// Name of the error kind used in the JSON output.
+    pub fn get_kind(&self) -> &'static str {
+        match self {
+            RukataError::InvalidSettings(_) => "invalid_settings",
+            RukataError::UnknownPuzzle(_) => "unknown_puzzle",
+            RukataError::TamperedFile(_) => "tampered_file",
+            RukataError::CargoFailure(_) => "cargo_failure",
+            RukataError::Io(_) => "io",
+        }
+    }
+
+    /// Process exit code for the error kind, documented in the README.
+    pub fn get_exit_code(&self) -> u8 {
+        match self {
+            RukataError::InvalidSettings(_) => 3,
+            RukataError::UnknownPuzzle(_) => 4,
+            RukataError::TamperedFile(_) => 5,
+            RukataError::CargoFailure(_) => 6,
+            RukataError::Io(_) => 7,
+        }
+    }
+}
+
+impl fmt::Display for RukataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RukataError::UnknownPuzzle(puzzle_id) => {
+                write!(f, "Puzzle ID is not valid {}", puzzle_id)
+            }
+            RukataError::InvalidSettings(message)
+            | RukataError::TamperedFile(message)
+            | RukataError::CargoFailure(message)
+            | RukataError::Io(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Serialize for RukataError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RukataError", 2)?;
+        state.serialize_field("kind", self.get_kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
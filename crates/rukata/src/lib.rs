@@ -1,5 +1,10 @@
 pub mod argument_builder;
+pub(crate) mod cargo;
 pub mod command;
 pub mod commands;
 pub(crate) mod common;
+pub(crate) mod diagnostics;
+pub mod error;
+pub mod reporter;
+pub(crate) mod test_report;
 pub mod validation;
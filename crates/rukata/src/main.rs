@@ -1,26 +1,58 @@
 use clap::FromArgMatches;
-use rukata::argument_builder::{generate_command, SubCommands};
+use rukata::argument_builder::{generate_command, GlobalArguments, SubCommands};
 use rukata::command::{Command, CommandHandler};
 use rukata::commands::check::CheckCommand;
+use rukata::commands::diff::DiffCommand;
 use rukata::commands::generate::GenerateCommand;
+use rukata::commands::hint::HintCommand;
+use rukata::commands::list::ListCommand;
+use rukata::commands::next::NextCommand;
+use rukata::commands::progress::ProgressCommand;
+use rukata::commands::redo::RedoCommand;
+use rukata::commands::reset::ResetCommand;
+use rukata::commands::review::ReviewCommand;
+use rukata::commands::search::SearchCommand;
 use rukata::commands::settings::SettingsCommand;
 use rukata::commands::solution::SolutionCommand;
+use rukata::commands::track::TrackCommand;
+use rukata::commands::watch::WatchCommand;
+use rukata::reporter::Reporter;
+use std::process::ExitCode;
 
-fn main() {
+fn main() -> ExitCode {
     let cmd = generate_command();
 
     let matches = cmd.get_matches();
+    let global_arguments = GlobalArguments::from_arg_matches(&matches)
+        .map_err(|err| err.exit())
+        .unwrap();
     let derived_subcommands = SubCommands::from_arg_matches(&matches)
         .map_err(|err| err.exit())
         .unwrap();
 
+    let reporter = Reporter::new(
+        global_arguments.format.into(),
+        matches.subcommand_name().unwrap_or_default(),
+    );
+
     let command: Box<dyn Command> = match derived_subcommands {
         SubCommands::Check(arguments) => Box::new(CheckCommand::new(arguments)),
         SubCommands::Generate(arguments) => Box::new(GenerateCommand::new(arguments)),
         SubCommands::Solution(arguments) => Box::new(SolutionCommand::new(arguments)),
         SubCommands::Settings(arguments) => Box::new(SettingsCommand::new(arguments)),
+        SubCommands::Progress => Box::new(ProgressCommand::new()),
+        SubCommands::List(arguments) => Box::new(ListCommand::new(arguments)),
+        SubCommands::Search(arguments) => Box::new(SearchCommand::new(arguments)),
+        SubCommands::Reset(arguments) => Box::new(ResetCommand::new(arguments)),
+        SubCommands::Redo(arguments) => Box::new(RedoCommand::new(arguments)),
+        SubCommands::Review(arguments) => Box::new(ReviewCommand::new(arguments)),
+        SubCommands::Next(arguments) => Box::new(NextCommand::new(arguments)),
+        SubCommands::Track(arguments) => Box::new(TrackCommand::new(arguments)),
+        SubCommands::Diff(arguments) => Box::new(DiffCommand::new(arguments)),
+        SubCommands::Hint(arguments) => Box::new(HintCommand::new(arguments)),
+        SubCommands::Watch(arguments) => Box::new(WatchCommand::new(arguments)),
     };
 
-    let mut command_handler = CommandHandler::new(command);
+    let mut command_handler = CommandHandler::new(command, reporter);
     command_handler.run()
 }
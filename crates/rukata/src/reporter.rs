@@ -0,0 +1,222 @@
+use crate::error::RukataError;
+use console::{measure_text_width, pad_str, Alignment, Style, Term};
+use lazy_static::lazy_static;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::io::{self, Write};
+use std::ops::{Deref, Range};
+
+lazy_static! {
+    static ref CYAN_TITLE: Style = Style::new().bold().cyan();
+    static ref RED_TITLE: Style = Style::new().bold().red();
+    static ref WHITE: Style = Style::new().white();
+    static ref WHITE_BOLD: Style = Style::new().bold().white();
+    static ref GREEN: Style = Style::new().green();
+    static ref RED: Style = Style::new().red();
+    static ref CYAN: Style = Style::new().cyan();
+    static ref HIGHLIGHT: Style = Style::new().bold().yellow();
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Human,
+    Json,
+}
+
+/// Collects the output of a command.
+///
+/// Human output is printed as it happens. JSON output is gathered into a
+/// single result object which is printed by `finish`.
+pub struct Reporter {
+    format: OutputFormat,
+    command: String,
+    fields: Map<String, Value>,
+}
+
+fn print_common(display: &str, style: &Style) {
+    println!("{}", style.apply_to(display));
+}
+
+pub fn highlight(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut highlighted = String::new();
+    let mut position = 0;
+    for range in ranges {
+        highlighted.push_str(&WHITE.apply_to(&text[position..range.start]).to_string());
+        highlighted.push_str(&HIGHLIGHT.apply_to(&text[range.clone()]).to_string());
+        position = range.end;
+    }
+    highlighted.push_str(&WHITE.apply_to(&text[position..]).to_string());
+
+    highlighted
+}
+
+impl Reporter {
+    pub fn new<S: Into<String>>(format: OutputFormat, command: S) -> Self {
+        Self {
+            format,
+            command: command.into(),
+            fields: Map::new(),
+        }
+    }
+
+    pub fn is_human(&self) -> bool {
+        self.format == OutputFormat::Human
+    }
+
+    pub fn print_cyan_title<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            println!();
+            print_common(display.as_ref(), CYAN_TITLE.deref());
+        }
+    }
+
+    pub fn print_red_title<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            println!();
+            print_common(display.as_ref(), RED_TITLE.deref());
+        }
+    }
+
+    pub fn print_white<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            print_common(display.as_ref(), WHITE.deref());
+        }
+    }
+
+    pub fn print_white_bold<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            print_common(display.as_ref(), WHITE_BOLD.deref());
+        }
+    }
+
+    pub fn print_green<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            print_common(display.as_ref(), GREEN.deref());
+        }
+    }
+
+    pub fn print_red<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            print_common(display.as_ref(), RED.deref());
+        }
+    }
+
+    pub fn print_cyan<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            print_common(display.as_ref(), CYAN.deref());
+        }
+    }
+
+    /// Prints text that has already been styled.
+    pub fn print_styled<S: AsRef<str>>(&self, display: S) {
+        if self.is_human() {
+            println!("{}", display.as_ref());
+        }
+    }
+
+    /// Asks a yes or no question, answering no without asking when the output is not for a person.
+    pub fn confirm<S: AsRef<str>>(&self, question: S) -> bool {
+        let term = Term::stdout();
+        if !self.is_human() || !term.is_term() {
+            return false;
+        }
+
+        print!(
+            "{} ",
+            WHITE_BOLD.apply_to(format!("{} [y/N]", question.as_ref()))
+        );
+        if io::stdout().flush().is_err() {
+            return false;
+        }
+
+        term.read_line()
+            .map(|answer| matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+            .unwrap_or_default()
+    }
+
+    pub fn print_blank_line(&self) {
+        if self.is_human() {
+            println!();
+        }
+    }
+
+    pub fn print_table<S: AsRef<str>>(&self, headers: &[&str], rows: &[Vec<S>]) {
+        if !self.is_human() {
+            return;
+        }
+
+        let mut widths: Vec<usize> = headers.iter().map(|h| measure_text_width(h)).collect();
+        for row in rows {
+            for (index, cell) in row.iter().enumerate() {
+                widths[index] = widths[index].max(measure_text_width(cell.as_ref()));
+            }
+        }
+
+        let format_row = |cells: Vec<&str>| -> String {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| pad_str(cell, *width, Alignment::Left, None).to_string())
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        };
+
+        self.print_white_bold(format_row(headers.to_vec()));
+        for row in rows {
+            self.print_white(format_row(row.iter().map(|cell| cell.as_ref()).collect()));
+        }
+    }
+
+    /// Sets a field of the JSON result object.
+    pub fn set<V: Serialize>(&mut self, key: &str, value: V) {
+        if !self.is_human() {
+            self.fields.insert(
+                key.to_string(),
+                serde_json::to_value(value).unwrap_or_default(),
+            );
+        }
+    }
+
+    /// Prints a JSON object straight away, used by commands that never finish.
+    pub fn emit<V: Serialize>(&self, value: V) {
+        if !self.is_human() {
+            println!("{}", serde_json::to_string(&value).unwrap_or_default());
+        }
+    }
+
+    /// Prints the errors, or the JSON result object.
+    pub fn finish(&self, errors: &[RukataError]) {
+        match self.format {
+            OutputFormat::Human => {
+                if !errors.is_empty() {
+                    self.print_red_title("The following errors occurred:");
+                    for error in errors {
+                        self.print_white(format!("- {}", error));
+                    }
+                }
+            }
+            OutputFormat::Json => {
+                let mut result = Map::new();
+                result.insert("command".to_string(), json!(self.command));
+                result.insert(
+                    "status".to_string(),
+                    json!(if errors.is_empty() {
+                        "success"
+                    } else {
+                        "failure"
+                    }),
+                );
+                result.extend(self.fields.clone());
+                result.insert("errors".to_string(), json!(errors));
+
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result).unwrap_or_default()
+                );
+            }
+        }
+    }
+}
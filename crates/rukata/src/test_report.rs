@@ -0,0 +1,290 @@
+use serde::Serialize;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+impl fmt::Display for TestStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TestStatus::Passed => f.pad("ok"),
+            TestStatus::Failed => f.pad("FAILED"),
+            TestStatus::Ignored => f.pad("ignored"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct TestResult {
+    pub(crate) name: String,
+    pub(crate) status: TestStatus,
+    pub(crate) message: Option<String>,
+}
+
+fn parse_status(status: &str) -> Option<TestStatus> {
+    match status {
+        "ok" => Some(TestStatus::Passed),
+        "FAILED" => Some(TestStatus::Failed),
+        status if status.starts_with("ignored") => Some(TestStatus::Ignored),
+        _ => None,
+    }
+}
+
+fn attach_message(results: &mut [TestResult], section_start: usize, name: &str, lines: &[&str]) {
+    // The backtrace is left in the full log.
+    let message = lines
+        .iter()
+        .take_while(|line| **line != "stack backtrace:")
+        .filter(|line| !line.starts_with("note: run with `RUST_BACKTRACE"))
+        .copied()
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string();
+
+    if message.is_empty() {
+        return;
+    }
+
+    if let Some(result) = results[section_start..]
+        .iter_mut()
+        .find(|result| result.name == name)
+    {
+        result.message = Some(message);
+    }
+}
+
+/// Parses the output of the libtest harness into a result for each test.
+///
+/// Failure output is attached to the failed test it belongs to.
+pub(crate) fn parse_test_output(stdout: &str) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = Vec::new();
+
+    // Each test binary prints its own section starting with `running N tests`.
+    let mut section_start = 0;
+    let mut failure: Option<(String, Vec<&str>)> = None;
+
+    for line in stdout.lines() {
+        if line.starts_with("running ") && (line.ends_with(" tests") || line.ends_with(" test")) {
+            section_start = results.len();
+            continue;
+        }
+
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|line| line.strip_suffix(" stdout ----"))
+        {
+            if let Some((name, lines)) = failure.take() {
+                attach_message(&mut results, section_start, &name, &lines);
+            }
+            failure = Some((name.to_string(), Vec::new()));
+            continue;
+        }
+
+        if let Some((name, lines)) = &mut failure {
+            if line == "failures:" || line.starts_with("test result:") {
+                attach_message(&mut results, section_start, name, lines);
+                failure = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if let Some((name, status)) = line
+            .strip_prefix("test ")
+            .and_then(|line| line.rsplit_once(" ... "))
+        {
+            if let Some(status) = parse_status(status) {
+                results.push(TestResult {
+                    name: name.to_string(),
+                    status,
+                    message: None,
+                });
+            }
+        }
+    }
+
+    if let Some((name, lines)) = failure.take() {
+        attach_message(&mut results, section_start, &name, &lines);
+    }
+
+    results
+}
+
+pub(crate) fn count_status(results: &[TestResult], status: TestStatus) -> usize {
+    results
+        .iter()
+        .filter(|result| result.status == status)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Output of `cargo test --no-fail-fast` for a library with two test files, `one.rs` and
+    // `two.rs`, both holding an `add_test`. Only `one.rs` is run here.
+    static PASSING_OUTPUT: &str = "
+running 1 test
+test add_test ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+    // The unit tests of the library, `one.rs` and then `two.rs`.
+    static FAILING_OUTPUT: &str = "
+running 0 tests
+
+test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+
+running 1 test
+test add_test ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+
+running 4 tests
+test add_test ... FAILED
+test print_test ... FAILED
+test slow_test ... ignored, takes too long
+test zero_test ... ok
+
+failures:
+
+---- add_test stdout ----
+
+thread 'add_test' (26857) panicked at tests/two.rs:2:17:
+assertion `left == right` failed
+  left: 3
+ right: 4
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- print_test stdout ----
+Adding 2 and 2
+
+thread 'print_test' (26858) panicked at tests/two.rs:5:47:
+assertion `left == right` failed: 2 + 2 should be 5
+  left: 4
+ right: 5
+
+
+failures:
+    add_test
+    print_test
+
+test result: FAILED. 1 passed; 2 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+
+    // The failure of `add_test` in `two.rs` with `RUST_BACKTRACE=1`, cut down to two frames.
+    static BACKTRACE_OUTPUT: &str = "
+running 1 test
+test add_test ... FAILED
+
+failures:
+
+---- add_test stdout ----
+
+thread 'add_test' (26843) panicked at tests/two.rs:2:17:
+assertion `left == right` failed
+  left: 3
+ right: 4
+stack backtrace:
+   0: __rustc::rust_begin_unwind
+             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/std/src/panicking.rs:689:5
+   1: core::panicking::panic_fmt
+             at /rustc/59807616e1fa2540724bfbac14d7976d7e4a3860/library/core/src/panicking.rs:80:14
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+
+
+failures:
+    add_test
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+
+    fn get_summary(results: &[TestResult]) -> Vec<(&str, TestStatus)> {
+        results
+            .iter()
+            .map(|result| (result.name.as_str(), result.status))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_passing_output() {
+        let results = parse_test_output(PASSING_OUTPUT);
+        assert_eq!(
+            get_summary(&results),
+            vec![("add_test", TestStatus::Passed)]
+        );
+        assert_eq!(results[0].message, None);
+    }
+
+    #[test]
+    fn test_parse_failing_output() {
+        let results = parse_test_output(FAILING_OUTPUT);
+        assert_eq!(
+            get_summary(&results),
+            vec![
+                ("add_test", TestStatus::Passed),
+                ("add_test", TestStatus::Failed),
+                ("print_test", TestStatus::Failed),
+                ("slow_test", TestStatus::Ignored),
+                ("zero_test", TestStatus::Passed),
+            ]
+        );
+        assert_eq!(count_status(&results, TestStatus::Failed), 2);
+
+        // The failure belongs to the test of the same name in the binary that failed.
+        assert_eq!(results[0].message, None);
+        assert_eq!(
+            results[1].message.as_deref(),
+            Some(
+                "thread 'add_test' (26857) panicked at tests/two.rs:2:17:
+assertion `left == right` failed
+  left: 3
+ right: 4"
+            )
+        );
+
+        // What the test printed is kept with the panic message.
+        assert_eq!(
+            results[2].message.as_deref(),
+            Some(
+                "Adding 2 and 2
+
+thread 'print_test' (26858) panicked at tests/two.rs:5:47:
+assertion `left == right` failed: 2 + 2 should be 5
+  left: 4
+ right: 5"
+            )
+        );
+        assert_eq!(results[3].message, None);
+        assert_eq!(results[4].message, None);
+    }
+
+    #[test]
+    fn test_parse_backtrace_output() {
+        let results = parse_test_output(BACKTRACE_OUTPUT);
+        assert_eq!(
+            get_summary(&results),
+            vec![("add_test", TestStatus::Failed)]
+        );
+        assert_eq!(
+            results[0].message.as_deref(),
+            Some(
+                "thread 'add_test' (26843) panicked at tests/two.rs:2:17:
+assertion `left == right` failed
+  left: 3
+ right: 4"
+            )
+        );
+    }
+}